@@ -0,0 +1,201 @@
+//! Allocation statistics of the stages of a run.
+//!
+//! With the `count-allocations` feature the `aoc` crate installs `Counting`
+//! as the global allocator of whatever links it, and every stage `solve` runs
+//! reports how often it allocated, how much, and the most it had allocated at
+//! once. Without the feature nothing is counted and the stats are `None`.
+//! Counting costs a few atomic operations per allocation, which shows in the
+//! timings of allocation-heavy days.
+
+use serde::{Deserialize, Serialize};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// What a stage allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct AllocStats {
+    /// Allocations, counting each growing or shrinking reallocation.
+    pub allocations: u64,
+    /// Bytes of all the allocations, a reallocation counting its new size.
+    pub bytes: u64,
+    /// The most bytes the stage had allocated at once, beyond those that were
+    /// live when it started.
+    pub peak_bytes: u64,
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations of {}, peak {}",
+            self.allocations,
+            Bytes(self.bytes),
+            Bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// A byte count shown in B, KiB, MiB or GiB.
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit + 1 < UNITS.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{size:.1} {}", UNITS[unit])
+    }
+}
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+/// Held while a stage is measured, since the peak is reset for each stage.
+static MEASURING: Mutex<()> = Mutex::new(());
+
+/// The system allocator, counting what goes through it.
+pub struct Counting;
+
+impl Counting {
+    fn allocated(size: usize) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn freed(size: usize) {
+        LIVE.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Counting::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Counting::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Counting::freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Counting::freed(layout.size());
+            Counting::allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// True if allocations are counted, with the `count-allocations` feature.
+pub const fn enabled() -> bool {
+    cfg!(feature = "count-allocations")
+}
+
+/// Runs `f` and returns what it allocated, or `None` when allocations are
+/// not counted.
+///
+/// Only one stage is measured at a time: a `measure` on another thread waits
+/// for this one to finish, and `f` must not call `measure` itself. The counts
+/// are still those of the whole process, so allocations that other threads
+/// make meanwhile are counted too.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+    let _measuring = MEASURING.lock().unwrap_or_else(|e| e.into_inner());
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    let result = f();
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (result, Some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shows_stats() {
+        let stats = AllocStats {
+            allocations: 12,
+            bytes: 3 * 1024 * 1024 + 512 * 1024,
+            peak_bytes: 900,
+        };
+        assert_eq!(stats.to_string(), "12 allocations of 3.5 MiB, peak 900 B");
+        assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+        assert_eq!(Bytes(5 << 40).to_string(), "5120.0 GiB");
+    }
+
+    #[test]
+    #[cfg(feature = "count-allocations")]
+    fn counts_allocations() {
+        let (_, stats) = measure(|| {
+            let mut numbers = Vec::<u64>::with_capacity(1000);
+            numbers.push(1);
+            drop(numbers);
+            vec![0u8; 100]
+        });
+        let stats = stats.unwrap();
+        assert!(stats.allocations >= 2);
+        assert!(stats.bytes >= 8100);
+        assert!(stats.peak_bytes >= 8000);
+    }
+
+    #[test]
+    #[cfg(feature = "count-allocations")]
+    fn measures_one_stage_at_a_time() {
+        let threads = (0..4)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    measure(|| {
+                        let block = vec![1u8; 1 << 20];
+                        std::thread::yield_now();
+                        drop(block);
+                    })
+                    .1
+                    .unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+        for thread in threads {
+            let stats = thread.join().unwrap();
+            assert!((1 << 20..2 << 20).contains(&stats.peak_bytes));
+        }
+    }
+}
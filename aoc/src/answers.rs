@@ -0,0 +1,249 @@
+use crate::{Day, Source};
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::{panic, thread};
+use toml::Value;
+
+/// Known answers of a day, read from its `answers.toml`. Every input has a
+/// table of the parts whose answer is known. An example that is only meant
+/// for some of the parts lists them in `parts`, and parts that are not solved
+/// yet, which `aoc run --all` skips, are listed in `unsolved`:
+///
+/// ```toml
+/// [example1]
+/// parts = [1]
+/// part1 = 13
+///
+/// [input]
+/// part1 = 28538
+/// unsolved = [2]
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Answers {
+    answers: HashMap<(String, u8), String>,
+    parts: HashMap<String, Vec<u8>>,
+    unsolved: HashMap<String, Vec<u8>>,
+}
+
+/// How an answer compares to the known one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict<'a> {
+    Unknown,
+    Correct,
+    Wrong { expected: &'a str },
+}
+
+impl Answers {
+    pub fn parse(toml: &str) -> Result<Answers, String> {
+        let table = toml
+            .parse::<toml::Table>()
+            .map_err(|e| format!("Invalid answers file: {e}"))?;
+
+        let mut answers = HashMap::new();
+        let mut applies_to = HashMap::new();
+        let mut unsolved = HashMap::new();
+        for (input, parts) in table {
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("[{input}] should be a table of parts"))?;
+            for (part, answer) in parts {
+                let part = match part.as_str() {
+                    "part1" => 1,
+                    "part2" => 2,
+                    "parts" => {
+                        applies_to.insert(input.clone(), parse_parts(&input, part, answer)?);
+                        continue;
+                    }
+                    "unsolved" => {
+                        unsolved.insert(input.clone(), parse_parts(&input, part, answer)?);
+                        continue;
+                    }
+                    _ => return Err(format!("Unknown part {part} in [{input}]")),
+                };
+                let answer = match answer {
+                    Value::String(answer) => answer.clone(),
+                    Value::Integer(answer) => answer.to_string(),
+                    _ => return Err(format!("Answer of [{input}] part{part} is not a number")),
+                };
+                answers.insert((input.clone(), part), answer);
+            }
+        }
+        Ok(Answers {
+            answers,
+            parts: applies_to,
+            unsolved,
+        })
+    }
+
+    /// The parts the named input is meant for, all of them unless it lists
+    /// its `parts`.
+    pub fn parts(&self, input: &str) -> &[u8] {
+        self.parts.get(input).map_or(&[1, 2], Vec::as_slice)
+    }
+
+    /// The parts not solved yet for the named input.
+    pub fn unsolved(&self, input: &str) -> &[u8] {
+        self.unsolved.get(input).map_or(&[], Vec::as_slice)
+    }
+
+    /// The known answer of the part for the named input.
+    pub fn get(&self, input: &str, part: u8) -> Option<&str> {
+        self.answers
+            .get(&(input.to_string(), part))
+            .map(String::as_str)
+    }
+
+    pub fn check(&self, input: &str, part: u8, answer: Option<&str>) -> Verdict<'_> {
+        match self.get(input, part) {
+            None => Verdict::Unknown,
+            Some(expected) if answer == Some(expected) => Verdict::Correct,
+            Some(expected) => Verdict::Wrong { expected },
+        }
+    }
+}
+
+fn parse_parts(input: &str, key: &str, parts: &Value) -> Result<Vec<u8>, String> {
+    let invalid = || format!("{key} of [{input}] should be a list like [1, 2]");
+    parts
+        .as_array()
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|part| match part.as_integer() {
+            Some(part @ 1..=2) => Ok(part as u8),
+            _ => Err(invalid()),
+        })
+        .collect()
+}
+
+/// Stack size of a main thread, which solutions get when run by the runner
+/// but test threads do not.
+const MAIN_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Solves the part for the input and asserts the answer is the known one.
+/// When the answer is not known, says on stderr that the check is skipped,
+/// written past the test harness so that it shows even when output is
+/// captured.
+pub fn assert_answer(day: &Day, source: &Source, part: u8) {
+    let answers = day.answers().unwrap();
+    let name = source.name();
+    let Some(expected) = answers.get(&name, part) else {
+        let _ = writeln!(
+            io::stderr(),
+            "Skipped day {} part {part} with {name}: its answer is not in answers.toml",
+            day.number
+        );
+        return;
+    };
+    let aoc = day.load(source, Some(part)).unwrap();
+    let solve = day.solve;
+    let run = thread::Builder::new()
+        .stack_size(MAIN_STACK_SIZE)
+        .spawn(move || solve(&aoc))
+        .unwrap()
+        .join()
+        .unwrap_or_else(|e| panic::resume_unwind(e));
+    if let Some(e) = run.errors().next() {
+        panic!("Day {} part {part} with {name} failed\n{e:#}", day.number);
+    }
+    let answer = run.part(part).and_then(|run| run.answer.as_deref());
+    assert_eq!(
+        answer,
+        Some(expected),
+        "Day {} part {part} with {name}",
+        day.number
+    );
+}
+
+/// Asserts the part gives the known answer for every example meant for it.
+pub fn assert_example_answers(day: &Day, part: u8) {
+    let answers = day.answers().unwrap();
+    for (name, _) in day.examples {
+        if answers.parts(name).contains(&part) {
+            assert_answer(day, &Source::Example(Some(name.to_string())), part);
+        }
+    }
+}
+
+/// Generates the standard `part1_with_example`, `part1_with_input`,
+/// `part2_with_example` and `part2_with_input` tests of a day, checking the
+/// answers in its `answers.toml`.
+#[macro_export]
+macro_rules! answer_tests {
+    ($day:path) => {
+        #[test]
+        fn part1_with_example() {
+            $crate::answers::assert_example_answers(&$day, 1);
+        }
+
+        #[test]
+        fn part1_with_input() {
+            $crate::answers::assert_answer(&$day, &$crate::Source::Input, 1);
+        }
+
+        #[test]
+        fn part2_with_example() {
+            $crate::answers::assert_example_answers(&$day, 2);
+        }
+
+        #[test]
+        fn part2_with_input() {
+            $crate::answers::assert_answer(&$day, &$crate::Source::Input, 2);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+[example]
+parts = [1]
+part1 = 13
+
+[input]
+part1 = 28538
+part2 = "14616363770447"
+
+[stress]
+unsolved = [2]
+"#;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.get("example", 1), Some("13"));
+        assert_eq!(answers.get("example", 2), None);
+        assert_eq!(answers.get("input", 2), Some("14616363770447"));
+        assert_eq!(answers.parts("example"), [1]);
+        assert_eq!(answers.parts("input"), [1, 2]);
+        assert!(answers.unsolved("input").is_empty());
+        assert_eq!(answers.unsolved("stress"), [2]);
+        assert_eq!(Answers::parse("").unwrap(), Answers::default());
+    }
+
+    #[test]
+    fn parse_invalid_answers() {
+        assert!(Answers::parse("part1 = 1").is_err());
+        assert!(Answers::parse("[input]\npart3 = 1").is_err());
+        assert!(Answers::parse("[input]\npart1 = 1.5").is_err());
+        assert!(Answers::parse("[input]\nparts = [3]").is_err());
+        assert!(Answers::parse("[input]\nparts = 1").is_err());
+        assert!(Answers::parse("[input]\nunsolved = [0]").is_err());
+    }
+
+    #[test]
+    fn check_answers() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.check("example", 1, Some("13")), Verdict::Correct);
+        assert_eq!(
+            answers.check("example", 1, Some("12")),
+            Verdict::Wrong { expected: "13" }
+        );
+        assert_eq!(
+            answers.check("example", 1, None),
+            Verdict::Wrong { expected: "13" }
+        );
+        assert_eq!(answers.check("example", 2, Some("1")), Verdict::Unknown);
+    }
+}
@@ -0,0 +1,321 @@
+use crate::logging::TIMING;
+use crate::{history, log_run, report_answers, Aoc, Day, Error, Run};
+use log::{info, warn};
+use std::time::{Duration, Instant};
+
+/// How long a benchmark warms up and how many samples it takes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchOptions {
+    /// Runs done before measuring, to warm up caches and the allocator.
+    pub warmup: usize,
+    /// Number of measured runs. When not given, runs until `budget` is spent.
+    pub iterations: Option<usize>,
+    pub budget: Duration,
+    /// Name to save the results under, for comparing later runs against.
+    pub baseline: Option<String>,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: 3,
+            iterations: None,
+            budget: Duration::from_secs(3),
+            baseline: None,
+        }
+    }
+}
+
+impl BenchOptions {
+    /// Parses the value of `--bench`: an iteration count like `100`, or a
+    /// time budget like `10s` or `500ms`.
+    pub fn with_spec(spec: Option<&str>) -> Result<BenchOptions, String> {
+        let mut options = BenchOptions::default();
+        let Some(spec) = spec else {
+            return Ok(options);
+        };
+
+        let budget = if let Some(ms) = spec.strip_suffix("ms") {
+            ms.parse::<f64>().ok().map(|ms| ms / 1000.0)
+        } else if let Some(s) = spec.strip_suffix('s') {
+            s.parse::<f64>().ok()
+        } else {
+            None
+        };
+
+        let budget = budget
+            .filter(|secs| *secs > 0.0)
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok());
+        match (budget, spec.parse::<usize>()) {
+            (Some(budget), _) => options.budget = budget,
+            (_, Ok(n)) if n > 0 => options.iterations = Some(n),
+            _ => return Err(format!("Not an iteration count or a time: {spec}")),
+        }
+        Ok(options)
+    }
+
+    /// Reads `--bench [N|TIME]`, `--warmup N` and `--baseline NAME` from the
+    /// arguments. Returns `None` when `--bench` is not given, and fails if
+    /// the other two are given without it.
+    pub fn from_args(args: &[String]) -> Result<Option<BenchOptions>, String> {
+        let mut options = None;
+        let mut warmup = None;
+        let mut baseline = None;
+        let mut args = args.iter().peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--bench" => {
+                    let spec = args.next_if(|spec| !spec.starts_with("--"));
+                    options = Some(BenchOptions::with_spec(spec.map(String::as_str))?);
+                }
+                "--warmup" => {
+                    let n = args.next().ok_or("--warmup needs a number of runs")?;
+                    warmup = Some(n.parse().map_err(|_| format!("Not a number: {n}"))?);
+                }
+                "--baseline" => {
+                    let name = args.next().ok_or("--baseline needs a name")?;
+                    baseline = Some(name.clone());
+                }
+                _ => {}
+            }
+        }
+
+        match &mut options {
+            Some(options) => {
+                options.warmup = warmup.unwrap_or(options.warmup);
+                options.baseline = baseline;
+            }
+            None if warmup.is_some() => return Err("--warmup only works with --bench".into()),
+            None if baseline.is_some() => return Err("--baseline only works with --bench".into()),
+            None => {}
+        }
+        Ok(options)
+    }
+}
+
+/// Summary statistics of a set of timing samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    /// Samples outside 1.5 interquartile ranges from the quartiles.
+    pub outliers: usize,
+}
+
+impl Stats {
+    /// Computes the statistics of `samples`, which must not be empty.
+    pub fn new(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "No samples");
+        let mut sorted = samples
+            .iter()
+            .map(Duration::as_secs_f64)
+            .collect::<Vec<_>>();
+        sorted.sort_by(f64::total_cmp);
+
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = if sorted.len() > 1 {
+            sorted.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        let q1 = quantile(&sorted, 0.25);
+        let q3 = quantile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        Stats {
+            samples: sorted.len(),
+            min: Duration::from_secs_f64(sorted[0]),
+            median: Duration::from_secs_f64(quantile(&sorted, 0.5)),
+            mean: Duration::from_secs_f64(mean),
+            p95: Duration::from_secs_f64(quantile(&sorted, 0.95)),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            outliers: sorted.iter().filter(|s| **s < low || **s > high).count(),
+        }
+    }
+}
+
+/// Linearly interpolated quantile of sorted samples.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let (i, fract) = (pos.floor() as usize, pos.fract());
+    match sorted.get(i + 1) {
+        Some(next) => sorted[i] + (next - sorted[i]) * fract,
+        None => sorted[i],
+    }
+}
+
+/// Timing statistics of every stage of a benchmarked day.
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub warmup: usize,
+    pub parse: Stats,
+    pub parts: Vec<(u8, Stats)>,
+    pub total: Stats,
+    /// The last measured run, for its answers.
+    pub last: Run,
+}
+
+/// Runs the day repeatedly, logs statistics for each stage and appends them
+/// to the benchmark history. A day that fails is run and reported once, and
+/// not benchmarked.
+pub fn run(day: &Day, aoc: &Aoc, options: &BenchOptions) -> Result<BenchReport, Error> {
+    let first = (day.solve)(aoc);
+    if let Some(error) = first.errors().next() {
+        let error = error.clone();
+        log_run(day, aoc, &first);
+        return Err(error);
+    }
+    for _ in 0..options.warmup {
+        (day.solve)(aoc);
+    }
+
+    let mut runs = vec![];
+    let start = Instant::now();
+    while match options.iterations {
+        Some(n) => runs.len() < n,
+        None => runs.is_empty() || start.elapsed() < options.budget,
+    } {
+        runs.push((day.solve)(aoc));
+    }
+
+    let stats =
+        |time: &dyn Fn(&Run) -> Duration| Stats::new(&runs.iter().map(time).collect::<Vec<_>>());
+    let last = runs.last().unwrap().clone();
+    let report = BenchReport {
+        warmup: options.warmup,
+        parse: stats(&|run| run.parse),
+        parts: last
+            .parts
+            .iter()
+            .map(|p| (p.part, stats(&|run| run.part(p.part).unwrap().time)))
+            .collect(),
+        total: stats(&Run::total),
+        last,
+    };
+    log_report(day, aoc, &report);
+
+    let records = history::records(
+        day.number,
+        aoc.input_name(),
+        &report,
+        options.baseline.as_deref(),
+    );
+    if let Err(e) = history::append(history::HISTORY_FILE, &records) {
+        warn!("Could not save to {}: {e}", history::HISTORY_FILE);
+    }
+    Ok(report)
+}
+
+fn log_report(day: &Day, aoc: &Aoc, report: &BenchReport) {
+    info!(target: TIMING, "Input: {}", aoc.source());
+    if let Some(allocs) = report.last.parse_allocs {
+        info!(target: TIMING, "Parse: {allocs}");
+    }
+    report_answers(day, aoc, &report.last);
+    info!(
+        target: TIMING,
+        "Benchmark: {} runs after {} warmup runs",
+        report.total.samples, report.warmup
+    );
+    info!(
+        target: TIMING,
+        "{:<8}{:>12}{:>12}{:>12}{:>12}{:>12}{:>10}",
+        "Stage", "min", "median", "mean", "p95", "stddev", "outliers"
+    );
+    let parts = report
+        .parts
+        .iter()
+        .map(|(part, stats)| (format!("Part {part}"), stats));
+    for (stage, stats) in [("Parse".to_string(), &report.parse)]
+        .into_iter()
+        .chain(parts)
+        .chain([("Total".to_string(), &report.total)])
+    {
+        info!(
+            target: TIMING,
+            "{:<8}{:>12.3?}{:>12.3?}{:>12.3?}{:>12.3?}{:>12.3?}{:>10}",
+            stage, stats.min, stats.median, stats.mean, stats.p95, stats.stddev, stats.outliers
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().map(|ms| Duration::from_millis(*ms)).collect()
+    }
+
+    #[test]
+    fn stats_of_samples() {
+        let stats = Stats::new(&millis(&[4, 1, 3, 2, 5]));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_micros(4800));
+        assert_eq!(stats.stddev.as_micros(), 1581);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn stats_detect_outliers() {
+        let stats = Stats::new(&millis(&[10, 11, 10, 12, 11, 10, 90]));
+        assert_eq!(stats.median, Duration::from_millis(11));
+        assert_eq!(stats.outliers, 1);
+    }
+
+    #[test]
+    fn stats_of_one_sample() {
+        let stats = Stats::new(&millis(&[7]));
+        assert_eq!(stats.p95, Duration::from_millis(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn bench_spec() {
+        let options = BenchOptions::with_spec(Some("250")).unwrap();
+        assert_eq!(options.iterations, Some(250));
+        let options = BenchOptions::with_spec(Some("1.5s")).unwrap();
+        assert_eq!(options.budget, Duration::from_millis(1500));
+        let options = BenchOptions::with_spec(Some("200ms")).unwrap();
+        assert_eq!(options.budget, Duration::from_millis(200));
+        assert!(BenchOptions::with_spec(Some("fast")).is_err());
+        assert!(BenchOptions::with_spec(Some("0")).is_err());
+        assert!(BenchOptions::with_spec(Some("1e30s")).is_err());
+        assert!(BenchOptions::with_spec(Some("infs")).is_err());
+        assert!(BenchOptions::with_spec(Some("NaNms")).is_err());
+    }
+
+    #[test]
+    fn bench_options_from_args() {
+        let args = [
+            "example",
+            "--warmup",
+            "1",
+            "--bench",
+            "10",
+            "--baseline",
+            "v1",
+        ]
+        .map(String::from);
+        let options = BenchOptions::from_args(&args).unwrap().unwrap();
+        assert_eq!(options.warmup, 1);
+        assert_eq!(options.iterations, Some(10));
+        assert_eq!(options.baseline.as_deref(), Some("v1"));
+        assert_eq!(BenchOptions::from_args(&args[..1]).unwrap(), None);
+        assert_eq!(
+            BenchOptions::from_args(&args[..3]).unwrap_err(),
+            "--warmup only works with --bench"
+        );
+        assert!(BenchOptions::from_args(&args[5..]).is_err());
+    }
+}
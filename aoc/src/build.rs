@@ -0,0 +1,63 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Finds the `example*.txt` files in `dir`, sorted by name. The name of an
+/// example is its file name without the extension.
+pub fn find_examples(dir: &Path) -> io::Result<Vec<(String, PathBuf)>> {
+    let mut examples = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|name| name.to_str());
+        if let Some(name) = name.and_then(|name| name.strip_suffix(".txt")) {
+            if name.starts_with("example") && path.is_file() {
+                examples.push((name.to_string(), path));
+            }
+        }
+    }
+    examples.sort();
+    Ok(examples)
+}
+
+/// Embeds the examples of the day being built. Call it from the day's
+/// `build.rs` and use the result as its examples:
+///
+/// ```text
+/// examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+/// ```
+pub fn embed_examples() {
+    let dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let examples = find_examples(&dir).expect("Could not read the day directory");
+
+    let entries = examples
+        .iter()
+        .map(|(name, path)| format!("({name:?}, include_str!({:?}))", path.display()))
+        .collect::<Vec<_>>();
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, format!("&[{}]", entries.join(", "))).unwrap();
+
+    println!("cargo:rerun-if-changed={}", dir.display());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_examples() {
+        let dir = env::temp_dir().join(format!("aoc_examples_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for file in ["example2.txt", "example1.txt", "input.txt", "example.md"] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+
+        let names = find_examples(&dir)
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["example1", "example2"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,113 @@
+use crate::error::{offset_in, Error};
+
+/// Keeps track of where in the input a solution is while it parses, so that
+/// errors point at the line and column they are about.
+///
+/// ```
+/// # use aoc::context::ParseContext;
+/// let context = ParseContext::new("1 2\n3 x\n");
+/// let line = context.lines().nth(1).unwrap();
+/// let error = line.error(&line.text[2..], "Not a number");
+/// assert_eq!((error.line, error.column), (Some(2), Some(3)));
+/// assert_eq!(context.error(&line.text[2..], "Not a number"), error);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ParseContext<'a> {
+    input: &'a str,
+}
+
+/// A line of the input and its line number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    /// Line number, starting from 1.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> ParseContext<'a> {
+    pub fn new(input: &'a str) -> ParseContext<'a> {
+        ParseContext { input }
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Line<'a>> {
+        self.input.lines().enumerate().map(|(i, text)| Line {
+            number: i + 1,
+            text,
+        })
+    }
+
+    /// The line that `token`, a slice of the input, is on.
+    pub fn line_of(&self, token: &str) -> Option<Line<'a>> {
+        let offset = offset_in(self.input, token)?;
+        let start = self.input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let end = self.input[offset..]
+            .find('\n')
+            .map_or(self.input.len(), |i| offset + i);
+        Some(Line {
+            number: self.input[..start].matches('\n').count() + 1,
+            text: self.input[start..end].trim_end_matches('\r'),
+        })
+    }
+
+    /// An error about `token`, pointing at where it is in the input when it
+    /// is a slice of it.
+    pub fn error(&self, token: &str, message: impl Into<String>) -> Error {
+        match self.line_of(token) {
+            Some(line) => line.error(token, message),
+            None => Error::new(message).with_token(token),
+        }
+    }
+}
+
+impl<'a> Line<'a> {
+    /// An error about `token`, a slice of this line.
+    pub fn error(&self, token: &str, message: impl Into<String>) -> Error {
+        Error::new(message)
+            .at_token(self.text, token)
+            .at_line(self.number)
+            .with_snippet(self.text)
+    }
+
+    /// Puts an error found in `part`, a slice of this line, on this line,
+    /// moving its column to where it is in the line.
+    /// ```
+    /// # use aoc::context::ParseContext;
+    /// # use aoc::try_extract_numbers;
+    /// let line = ParseContext::new("Card 1: 41 | 8x3 300").lines().next().unwrap();
+    /// let part = &line.text[12..];
+    /// let error = try_extract_numbers::<u8>(part).find_map(Result::err).unwrap();
+    /// assert_eq!(line.locate(error, part).to_string(), r#"line 1, column 18: Not a number: "300""#);
+    /// ```
+    pub fn locate(&self, error: Error, part: &str) -> Error {
+        let skipped =
+            offset_in(self.text, part).map_or(0, |offset| self.text[..offset].chars().count());
+        error
+            .shifted(skipped)
+            .at_line(self.number)
+            .with_snippet(self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_tokens() {
+        let input = "32T3K 765\nT55J5 684\r\nKK677 x\n";
+        let context = ParseContext::new(input);
+        let bid = &input[input.len() - 2..input.len() - 1];
+        assert_eq!(
+            context.line_of(bid),
+            Some(Line {
+                number: 3,
+                text: "KK677 x"
+            })
+        );
+        assert_eq!(context.line_of(&input[16..17]).unwrap().text, "T55J5 684");
+
+        let error = context.error(bid, "Bid not a number");
+        assert_eq!((error.line, error.column), (Some(3), Some(7)));
+        assert_eq!(context.error("x", "Bid not a number").line, None);
+    }
+}
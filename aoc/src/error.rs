@@ -0,0 +1,217 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A failure to read or parse an input, with where in the input it happened
+/// when that is known.
+///
+/// Displayed on one line, or with `{:#}` like a compiler diagnostic showing
+/// the line of the input with a caret under the token when it is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub message: String,
+    pub file: Option<Box<Path>>,
+    /// Line number, starting from 1.
+    pub line: Option<usize>,
+    /// Column in characters, starting from 1.
+    pub column: Option<usize>,
+    /// The text that could not be parsed.
+    pub token: Option<String>,
+    /// The text of the line the error is on.
+    pub snippet: Option<String>,
+}
+
+impl Error {
+    pub fn new(message: impl Into<String>) -> Error {
+        Error {
+            message: message.into(),
+            file: None,
+            line: None,
+            column: None,
+            token: None,
+            snippet: None,
+        }
+    }
+
+    pub fn in_file(mut self, file: impl Into<PathBuf>) -> Error {
+        self.file = Some(file.into().into_boxed_path());
+        self
+    }
+
+    /// Sets the line, for errors found in a single line of the input.
+    pub fn at_line(mut self, line: usize) -> Error {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn at(self, line: usize, column: usize) -> Error {
+        Error {
+            column: Some(column),
+            ..self.at_line(line)
+        }
+    }
+
+    /// Sets the token, and the column to where it starts in `text` when it is
+    /// a slice of `text`.
+    pub fn at_token(mut self, text: &str, token: &str) -> Error {
+        if let Some(offset) = offset_in(text, token) {
+            self.column = Some(text[..offset].chars().count() + 1);
+        }
+        self.with_token(token)
+    }
+
+    /// Moves the column right by `columns`, for an error found in a part of a
+    /// line that starts that many characters into it.
+    pub fn shifted(mut self, columns: usize) -> Error {
+        self.column = self.column.map(|column| column + columns);
+        self
+    }
+
+    pub fn with_token(mut self, token: impl Into<String>) -> Error {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn with_snippet(mut self, line: impl Into<String>) -> Error {
+        self.snippet = Some(line.into());
+        self
+    }
+
+    /// Sets the file unless the error already has one.
+    pub fn or_file(self, file: impl Into<PathBuf>) -> Error {
+        match self.file {
+            Some(_) => self,
+            None => self.in_file(file),
+        }
+    }
+
+    /// Writes the error with the line it is on and a caret under the token.
+    fn render(&self, f: &mut fmt::Formatter<'_>, line: usize, snippet: &str) -> fmt::Result {
+        write!(f, "error: {}", self.message)?;
+        if let Some(token) = &self.token {
+            write!(f, ": {token:?}")?;
+        }
+        let gutter = " ".repeat(line.to_string().len());
+        write!(f, "\n{gutter}--> ")?;
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(f, "{line}")?;
+        if let Some(column) = self.column {
+            write!(f, ":{column}")?;
+        }
+        write!(f, "\n{gutter} |\n{line} | {snippet}\n{gutter} |")?;
+        if let Some(column) = self.column {
+            let width = self.token.as_ref().map_or(1, |token| token.chars().count());
+            write!(
+                f,
+                " {}{}",
+                " ".repeat(column.saturating_sub(1)),
+                "^".repeat(width.max(1))
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Where `token` starts in `text`, if it is a slice of it.
+pub(crate) fn offset_in(text: &str, token: &str) -> Option<usize> {
+    (token.as_ptr() as usize)
+        .checked_sub(text.as_ptr() as usize)
+        .filter(|offset| offset + token.len() <= text.len() && text.is_char_boundary(*offset))
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let (true, Some(line), Some(snippet)) = (f.alternate(), self.line, &self.snippet) {
+            return self.render(f, line, snippet);
+        }
+        match (&self.file, self.line, self.column) {
+            (Some(file), line, column) => {
+                write!(f, "{}", file.display())?;
+                for n in [line, column].into_iter().flatten() {
+                    write!(f, ":{n}")?;
+                }
+                write!(f, ": ")?;
+            }
+            (None, Some(line), Some(column)) => write!(f, "line {line}, column {column}: ")?,
+            (None, Some(line), None) => write!(f, "line {line}: ")?,
+            (None, None, Some(column)) => write!(f, "column {column}: ")?,
+            (None, None, None) => {}
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(token) = &self.token {
+            write!(f, ": {token:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Error {
+        Error::new(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shows_position() {
+        let line = "9 4x 200";
+        let error = Error::new("Not a number").at_token(line, &line[2..4]);
+        assert_eq!(error.column, Some(3));
+        assert_eq!(error.to_string(), r#"column 3: Not a number: "4x""#);
+        assert_eq!(
+            error.clone().at_line(7).to_string(),
+            r#"line 7, column 3: Not a number: "4x""#
+        );
+        assert_eq!(
+            error.at_line(7).in_file("input.txt").to_string(),
+            r#"input.txt:7:3: Not a number: "4x""#
+        );
+        assert_eq!(Error::new("Empty input").to_string(), "Empty input");
+        assert_eq!(Error::new("Bad").at(1, 2).shifted(5).column, Some(7));
+        assert_eq!(Error::new("Bad").shifted(5).column, None);
+    }
+
+    #[test]
+    fn renders_caret_under_token() {
+        let line = "32X3K 765";
+        let error = Error::new("Card not found")
+            .at_token(line, &line[2..3])
+            .at_line(12)
+            .with_snippet(line)
+            .in_file("input.txt");
+        assert_eq!(
+            format!("{error:#}"),
+            "error: Card not found: \"X\"\n  \
+             --> input.txt:12:3\n   |\n12 | 32X3K 765\n   |   ^"
+        );
+        assert_eq!(
+            format!("{:#}", Error::new("No start")),
+            "No start",
+            "Without a line the error shows on one line"
+        );
+        assert_eq!(
+            format!("{:#}", Error::new("Bad").at(1, 0).with_snippet("abc")),
+            "error: Bad\n --> 1:0\n  |\n1 | abc\n  | ^",
+            "Column 0 puts the caret on the first column"
+        );
+    }
+
+    #[test]
+    fn token_must_be_inside_text() {
+        let input = "ab\ncd";
+        assert_eq!(offset_in(input, &input[3..]), Some(3));
+        assert_eq!(offset_in(&input[..3], &input[2..5]), None);
+        assert_eq!(offset_in(&input[3..], &input[..2]), None);
+        let error = Error::new("Bad").at_token(&input[..2], &input[3..]);
+        assert_eq!(error.column, None);
+    }
+}
@@ -0,0 +1,318 @@
+use crate::grid::{Grid, Pos};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// A point with unsigned coordinates, like a position in a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub y: usize,
+    pub x: usize,
+}
+
+/// A point with signed coordinates, on a plane without edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct IPoint {
+    pub y: isize,
+    pub x: isize,
+}
+
+/// The difference between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vector {
+    pub dy: isize,
+    pub dx: isize,
+}
+
+impl Point {
+    pub const fn new(y: usize, x: usize) -> Point {
+        Point { y, x }
+    }
+
+    /// The point `vector` away, if neither coordinate goes below zero.
+    pub fn checked_add(self, vector: impl Into<Vector>) -> Option<Point> {
+        let vector = vector.into();
+        Some(Point {
+            y: self.y.checked_add_signed(vector.dy)?,
+            x: self.x.checked_add_signed(vector.dx)?,
+        })
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        self.y.abs_diff(other.y) + self.x.abs_diff(other.x)
+    }
+
+    pub fn chebyshev(self, other: Point) -> usize {
+        self.y.abs_diff(other.y).max(self.x.abs_diff(other.x))
+    }
+}
+
+impl IPoint {
+    pub const fn new(y: isize, x: isize) -> IPoint {
+        IPoint { y, x }
+    }
+
+    pub fn manhattan(self, other: IPoint) -> usize {
+        self.y.abs_diff(other.y) + self.x.abs_diff(other.x)
+    }
+
+    pub fn chebyshev(self, other: IPoint) -> usize {
+        self.y.abs_diff(other.y).max(self.x.abs_diff(other.x))
+    }
+}
+
+impl Vector {
+    pub const fn new(dy: isize, dx: isize) -> Vector {
+        Vector { dy, dx }
+    }
+}
+
+impl From<Pos> for Point {
+    fn from((y, x): Pos) -> Point {
+        Point { y, x }
+    }
+}
+
+impl From<Point> for Pos {
+    fn from(point: Point) -> Pos {
+        (point.y, point.x)
+    }
+}
+
+impl From<Point> for IPoint {
+    fn from(point: Point) -> IPoint {
+        IPoint::new(point.y as isize, point.x as isize)
+    }
+}
+
+impl Add<Vector> for IPoint {
+    type Output = IPoint;
+
+    fn add(self, vector: Vector) -> IPoint {
+        IPoint::new(self.y + vector.dy, self.x + vector.dx)
+    }
+}
+
+impl AddAssign<Vector> for IPoint {
+    fn add_assign(&mut self, vector: Vector) {
+        *self = *self + vector;
+    }
+}
+
+impl Sub for IPoint {
+    type Output = Vector;
+
+    fn sub(self, other: IPoint) -> Vector {
+        Vector::new(self.y - other.y, self.x - other.x)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.dy + other.dy, self.dx + other.dx)
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Vector;
+
+    fn mul(self, n: isize) -> Vector {
+        Vector::new(self.dy * n, self.dx * n)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.dy, -self.dx)
+    }
+}
+
+/// The four directions along the rows and columns of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction4 {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction4 {
+    /// In the order of `Grid::neighbors4`.
+    pub const ALL: [Direction4; 4] = [
+        Direction4::Up,
+        Direction4::Down,
+        Direction4::Left,
+        Direction4::Right,
+    ];
+
+    pub fn reverse(self) -> Direction4 {
+        match self {
+            Direction4::Up => Direction4::Down,
+            Direction4::Down => Direction4::Up,
+            Direction4::Left => Direction4::Right,
+            Direction4::Right => Direction4::Left,
+        }
+    }
+
+    /// Turns a quarter clockwise.
+    pub fn turn_right(self) -> Direction4 {
+        match self {
+            Direction4::Up => Direction4::Right,
+            Direction4::Right => Direction4::Down,
+            Direction4::Down => Direction4::Left,
+            Direction4::Left => Direction4::Up,
+        }
+    }
+
+    /// Turns a quarter counterclockwise.
+    pub fn turn_left(self) -> Direction4 {
+        self.turn_right().reverse()
+    }
+
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction4::Up => Vector::new(-1, 0),
+            Direction4::Down => Vector::new(1, 0),
+            Direction4::Left => Vector::new(0, -1),
+            Direction4::Right => Vector::new(0, 1),
+        }
+    }
+}
+
+/// The eight directions to the neighbors of a cell, diagonals included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Clockwise from up.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    pub fn reverse(self) -> Direction8 {
+        self.turned(4)
+    }
+
+    /// Turns an eighth clockwise.
+    pub fn turn_right(self) -> Direction8 {
+        self.turned(1)
+    }
+
+    /// Turns an eighth counterclockwise.
+    pub fn turn_left(self) -> Direction8 {
+        self.turned(7)
+    }
+
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction8::Up => Vector::new(-1, 0),
+            Direction8::UpRight => Vector::new(-1, 1),
+            Direction8::Right => Vector::new(0, 1),
+            Direction8::DownRight => Vector::new(1, 1),
+            Direction8::Down => Vector::new(1, 0),
+            Direction8::DownLeft => Vector::new(1, -1),
+            Direction8::Left => Vector::new(0, -1),
+            Direction8::UpLeft => Vector::new(-1, -1),
+        }
+    }
+
+    /// The direction `eighths` eighths clockwise.
+    fn turned(self, eighths: usize) -> Direction8 {
+        Direction8::ALL[(self as usize + eighths) % 8]
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Direction8 {
+        match direction {
+            Direction4::Up => Direction8::Up,
+            Direction4::Down => Direction8::Down,
+            Direction4::Left => Direction8::Left,
+            Direction4::Right => Direction8::Right,
+        }
+    }
+}
+
+impl From<Direction4> for Vector {
+    fn from(direction: Direction4) -> Vector {
+        direction.vector()
+    }
+}
+
+impl From<Direction8> for Vector {
+    fn from(direction: Direction8) -> Vector {
+        direction.vector()
+    }
+}
+
+impl<T> Grid<T> {
+    /// The position a step in `direction` from `pos`, if it is in the grid.
+    pub fn step(&self, pos: Pos, direction: impl Into<Vector>) -> Option<Pos> {
+        let vector = direction.into();
+        self.offset(pos, (vector.dy, vector.dx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns_and_reverses() {
+        assert_eq!(Direction4::Up.turn_right(), Direction4::Right);
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+        assert_eq!(Direction4::Left.reverse(), Direction4::Right);
+        for direction in Direction4::ALL {
+            assert_eq!(direction.vector(), -direction.reverse().vector());
+            assert_eq!(
+                Direction8::from(direction).turn_right().turn_right(),
+                direction.turn_right().into()
+            );
+        }
+        assert_eq!(Direction8::UpLeft.turn_right(), Direction8::Up);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownRight.reverse(), Direction8::UpLeft);
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point::new(1, 6), Point::new(4, 2));
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (7, 4));
+        let (a, b) = (IPoint::new(-1, 6), IPoint::new(4, -2));
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (13, 8));
+        assert_eq!(b - a, Vector::new(5, -8));
+        assert_eq!(a + (b - a) * 2, IPoint::new(9, -10));
+    }
+
+    #[test]
+    fn steps_within_bounds() {
+        let grid = Grid::filled(3, 2, ());
+        assert_eq!(grid.step((0, 0), Direction4::Right), Some((0, 1)));
+        assert_eq!(grid.step((0, 0), Direction4::Up), None);
+        assert_eq!(grid.step((1, 1), Direction8::DownLeft), None);
+        assert_eq!(grid.step((0, 1), Direction8::DownLeft), Some((1, 0)));
+        assert_eq!(
+            Point::new(0, 2).checked_add(Direction4::Left),
+            Some(Point::new(0, 1))
+        );
+        assert_eq!(Point::new(0, 2).checked_add(Direction4::Up), None);
+    }
+}
@@ -0,0 +1,476 @@
+use crate::context::ParseContext;
+use crate::Error;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+/// A position in a grid as `(row, column)`, counted from the top left.
+pub type Pos = (usize, usize);
+
+/// Offsets to the four neighbors: up, down, left and right.
+const NEIGHBORS4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Offsets to the eight neighbors, row by row.
+const NEIGHBORS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid of cells stored row by row in one vector.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells row by row. Panics if there are not
+    /// `width * height` of them.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "Not {width}x{height} cells");
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses a grid with a cell for each character of the lines of `input`,
+    /// failing at the first line that is not as long as the first one.
+    ///
+    /// ```
+    /// # use aoc::grid::Grid;
+    /// let grid = Grid::parse("#.\n.#\n", |c| c == '#').unwrap();
+    /// assert_eq!((grid.width(), grid.height()), (2, 2));
+    /// assert!(grid[(1, 1)] && !grid[(0, 1)]);
+    /// ```
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Grid<T>, Error> {
+        Grid::try_parse(input, |c| Ok::<_, Error>(cell(c)))
+    }
+
+    /// Parses a grid like `parse`, failing at the first character the mapper
+    /// fails on.
+    ///
+    /// ```
+    /// # use aoc::grid::Grid;
+    /// let digits = |c: char| c.to_digit(10).ok_or("Not a digit");
+    /// assert_eq!(Grid::try_parse("12\n34\n", digits).unwrap()[(1, 0)], 3);
+    /// let error = Grid::try_parse("12\n3x\n", digits).unwrap_err();
+    /// assert_eq!(error.to_string(), r#"line 2, column 2: Not a digit: "x""#);
+    /// ```
+    pub fn try_parse<E: Display>(
+        input: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Grid<T>, Error> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for line in ParseContext::new(input).lines() {
+            let start = cells.len();
+            for (i, c) in line.text.char_indices() {
+                let token = &line.text[i..i + c.len_utf8()];
+                cells.push(cell(c).map_err(|e| line.error(token, e.to_string()))?);
+            }
+            let length = cells.len() - start;
+            if *width.get_or_insert(length) != length {
+                return Err(line.error(line.text, format!("Line is not {} long", width.unwrap())));
+            }
+            height += 1;
+        }
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+
+    /// Creates a grid from its rows, failing if they are not all as long.
+    pub fn from_rows<R>(rows: impl IntoIterator<Item = R>) -> Result<Grid<T>, Error>
+    where
+        R: IntoIterator<Item = T>,
+    {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for row in rows {
+            let start = cells.len();
+            cells.extend(row);
+            let length = cells.len() - start;
+            if *width.get_or_insert(length) != length {
+                return Err(
+                    Error::new(format!("Row is not {} long", width.unwrap())).at_line(height + 1)
+                );
+            }
+            height += 1;
+        }
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, column): Pos) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.index(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if !self.contains(pos) {
+            return None;
+        }
+        let index = self.index(pos);
+        Some(&mut self.cells[index])
+    }
+
+    /// The position `offset` away from `pos`, if it is in the grid.
+    pub fn offset(&self, (row, column): Pos, (dr, dc): (isize, isize)) -> Option<Pos> {
+        let pos = (row.checked_add_signed(dr)?, column.checked_add_signed(dc)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The position at `row` and `column` on a grid repeating in every
+    /// direction. Panics if the grid is empty.
+    pub fn wrap(&self, row: isize, column: isize) -> Pos {
+        (
+            row.rem_euclid(self.height as isize) as usize,
+            column.rem_euclid(self.width as isize) as usize,
+        )
+    }
+
+    /// The cell at `row` and `column` on a grid repeating in every direction.
+    pub fn get_wrapping(&self, row: isize, column: isize) -> &T {
+        &self[self.wrap(row, column)]
+    }
+
+    /// The neighbors above, below, left and right of `pos` that are in the
+    /// grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS4
+            .iter()
+            .filter_map(move |offset| self.offset(pos, *offset))
+    }
+
+    /// The neighbors of `pos` that are in the grid, diagonals included.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS8
+            .iter()
+            .filter_map(move |offset| self.offset(pos, *offset))
+    }
+
+    /// The positions row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i / width, i % width))
+    }
+
+    /// The cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell, row by row, that matches.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        let i = self.cells.iter().position(predicate)?;
+        Some((i / self.width, i % self.width))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        assert!(column < self.width, "No column {column}");
+        self.cells.iter().skip(column).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// The rectangle of `height` rows and `width` columns with its top left
+    /// at `pos`. Panics if it does not fit in the grid.
+    pub fn view(&self, pos: Pos, height: usize, width: usize) -> View<'_, T> {
+        assert!(
+            pos.0 + height <= self.height && pos.1 + width <= self.width,
+            "View does not fit in the grid"
+        );
+        View {
+            grid: self,
+            top: pos.0,
+            left: pos.1,
+            width,
+            height,
+        }
+    }
+
+    /// The rows turned into columns, mirroring the grid along its diagonal.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |(row, column)| (column, row))
+    }
+
+    /// The grid turned a quarter clockwise.
+    pub fn rotate_right(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |(row, column)| {
+            (self.height - 1 - column, row)
+        })
+    }
+
+    /// The grid turned a quarter counterclockwise.
+    pub fn rotate_left(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |(row, column)| {
+            (column, self.width - 1 - row)
+        })
+    }
+
+    /// The grid mirrored left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearranged(self.width, self.height, |(row, column)| {
+            (row, self.width - 1 - column)
+        })
+    }
+
+    /// The grid mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearranged(self.width, self.height, |(row, column)| {
+            (self.height - 1 - row, column)
+        })
+    }
+
+    /// A grid of the given size whose cell at each position is the cell of
+    /// this grid at `from` of it.
+    fn rearranged(&self, width: usize, height: usize, from: impl Fn(Pos) -> Pos) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..width * height)
+            .map(|i| self[from((i / width, i % width))].clone())
+            .collect();
+        Grid::new(width, height, cells)
+    }
+
+    fn index(&self, (row, column): Pos) -> usize {
+        row * self.width + column
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "{pos:?} is not in the grid");
+        &self.cells[self.index(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "{pos:?} is not in the grid");
+        let index = self.index(pos);
+        &mut self.cells[index]
+    }
+}
+
+/// Shows the grid a row per line, like the input it was parsed from when
+/// the cells show as the characters they were parsed from.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_rows(f, self.rows())
+    }
+}
+
+/// A rectangle of a grid.
+#[derive(Debug, Clone, Copy)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    top: usize,
+    left: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cell at `pos` of the view.
+    pub fn get(&self, (row, column): Pos) -> Option<&'a T> {
+        (row < self.height && column < self.width)
+            .then(|| &self.grid[(self.top + row, self.left + column)])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let (grid, left, width) = (self.grid, self.left, self.width);
+        (self.top..self.top + self.height).map(move |row| &grid.row(row)[left..left + width])
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(
+            self.width,
+            self.height,
+            self.rows().flatten().cloned().collect(),
+        )
+    }
+}
+
+impl<T: Display> Display for View<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_rows(f, self.rows())
+    }
+}
+
+fn write_rows<'a, T: Display + 'a>(
+    f: &mut fmt::Formatter<'_>,
+    rows: impl Iterator<Item = &'a [T]>,
+) -> fmt::Result {
+    for row in rows {
+        for cell in row {
+            write!(f, "{cell}")?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |c| c).unwrap()
+    }
+
+    #[test]
+    fn parses_and_shows() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        let error = Grid::parse("abc\nde\n", |c| c).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 2, column 1: Line is not 3 long: "de""#
+        );
+        assert_eq!(Grid::parse("", |c| c).unwrap().height(), 0);
+        assert_eq!(
+            Grid::from_rows([vec![1, 2], vec![3, 4]]).unwrap(),
+            Grid::new(2, 2, vec![1, 2, 3, 4])
+        );
+        assert!(Grid::from_rows([vec![1, 2], vec![3]]).is_err());
+    }
+
+    #[test]
+    fn indexes_checked_and_wrapping() {
+        let grid = grid();
+        assert_eq!(grid.get((1, 2)), Some(&'f'));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (1, 2)), Some((1, 2)));
+        assert_eq!(grid.get_wrapping(-1, -1), &'f');
+        assert_eq!(grid.get_wrapping(4, 7), &'b');
+    }
+
+    #[test]
+    fn neighbors_in_bounds() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbors4((0, 1)).collect::<Vec<_>>(),
+            [(1, 1), (0, 0), (0, 2)]
+        );
+        assert_eq!(
+            grid.neighbors8((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0), (1, 1)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b', 'c'], ['d', 'e', 'f']]
+        );
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(grid.position(|c| *c == 'e'), Some((1, 1)));
+
+        let empty_rows = Grid::<char>::new(0, 2, vec![]);
+        assert_eq!(empty_rows.rows().count(), 2);
+        assert_eq!(empty_rows.to_string(), "\n\n");
+    }
+
+    #[test]
+    fn rearranges() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+    }
+
+    #[test]
+    fn views() {
+        let grid = grid();
+        let view = grid.view((0, 1), 2, 2);
+        assert_eq!(view.to_string(), "bc\nef\n");
+        assert_eq!(view.get((1, 0)), Some(&'e'));
+        assert_eq!(view.get((0, 2)), None);
+        assert_eq!(view.to_grid().transpose().to_string(), "be\ncf\n");
+    }
+}
@@ -0,0 +1,259 @@
+use crate::benchmark::BenchReport;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Where benchmark results are appended, one JSON record per line.
+pub const HISTORY_FILE: &str = "target/bench/history.jsonl";
+
+/// Timing statistics of one stage of one benchmark run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u8,
+    /// Name of the input, like `input` or `example`.
+    pub input: String,
+    /// `parse`, `part1`, `part2` or `total`.
+    pub stage: String,
+    /// Output of `git describe --always --dirty`.
+    pub commit: String,
+    pub machine: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Set when the run was saved with `--baseline NAME`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<String>,
+    pub samples: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub p95_ns: u64,
+    pub stddev_ns: u64,
+}
+
+impl Record {
+    fn is_same_stage(&self, other: &Record) -> bool {
+        self.day == other.day
+            && self.input == other.input
+            && self.stage == other.stage
+            && self.machine == other.machine
+    }
+}
+
+/// Turns a benchmark report into one record per stage.
+pub fn records(day: u8, input: &str, report: &BenchReport, baseline: Option<&str>) -> Vec<Record> {
+    let commit = commit();
+    let machine = machine();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+
+    let parts = report
+        .parts
+        .iter()
+        .map(|(part, stats)| (format!("part{part}"), stats));
+    [("parse".to_string(), &report.parse)]
+        .into_iter()
+        .chain(parts)
+        .chain([("total".to_string(), &report.total)])
+        .map(|(stage, stats)| Record {
+            day,
+            input: input.to_string(),
+            stage,
+            commit: commit.clone(),
+            machine: machine.clone(),
+            timestamp,
+            baseline: baseline.map(str::to_string),
+            samples: stats.samples,
+            min_ns: nanos(stats.min),
+            median_ns: nanos(stats.median),
+            mean_ns: nanos(stats.mean),
+            p95_ns: nanos(stats.p95),
+            stddev_ns: nanos(stats.stddev),
+        })
+        .collect()
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+/// Output of `git describe --always --dirty`, or `unknown` outside a
+/// repository.
+pub(crate) fn commit() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn machine() -> String {
+    fs::read_to_string("/etc/hostname")
+        .ok()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Appends the records to the history file, creating it if needed.
+pub fn append(path: impl AsRef<Path>, records: &[Record]) -> io::Result<()> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for record in records {
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+    }
+    Ok(())
+}
+
+/// Reads every record of the history file, oldest first.
+pub fn load(path: impl AsRef<Path>) -> io::Result<Vec<Record>> {
+    let path = path.as_ref();
+    let file = BufReader::new(fs::File::open(path)?);
+    let mut records = vec![];
+    for (i, line) in file.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(&line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}:{}: {e}", path.display(), i + 1),
+            )
+        })?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// The latest result of a stage and the result it is compared against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub before: Record,
+    pub after: Record,
+}
+
+impl Comparison {
+    /// Relative change of the median, `0.1` meaning 10% slower.
+    pub fn change(&self) -> f64 {
+        if self.before.median_ns == 0 {
+            return 0.0;
+        }
+        self.after.median_ns as f64 / self.before.median_ns as f64 - 1.0
+    }
+
+    /// True if the median got slower by more than `threshold`, `0.1` being 10%.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+/// Compares the latest result of every stage with the one before it, or with
+/// the latest result saved under the `baseline` name. Stages with nothing to
+/// compare against are left out.
+pub fn compare(records: &[Record], baseline: Option<&str>) -> Vec<Comparison> {
+    let mut comparisons: Vec<Comparison> = vec![];
+    for (i, after) in records.iter().enumerate().rev() {
+        if comparisons.iter().any(|c| c.after.is_same_stage(after)) {
+            continue;
+        }
+        let before = records[..i].iter().rev().find(|before| {
+            before.is_same_stage(after)
+                && baseline.is_none_or(|name| before.baseline.as_deref() == Some(name))
+        });
+        if let Some(before) = before {
+            comparisons.push(Comparison {
+                before: before.clone(),
+                after: after.clone(),
+            });
+        }
+    }
+    comparisons.reverse();
+    comparisons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(stage: &str, median_ns: u64, baseline: Option<&str>) -> Record {
+        Record {
+            day: 4,
+            input: "input".to_string(),
+            stage: stage.to_string(),
+            commit: "abc1234".to_string(),
+            machine: "box".to_string(),
+            timestamp: 0,
+            baseline: baseline.map(str::to_string),
+            samples: 10,
+            min_ns: median_ns,
+            median_ns,
+            mean_ns: median_ns,
+            p95_ns: median_ns,
+            stddev_ns: 0,
+        }
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc_history_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let records = vec![record("parse", 100, None), record("part1", 200, Some("v1"))];
+        append(&path, &records[..1]).unwrap();
+        append(&path, &records[1..]).unwrap();
+        assert_eq!(load(&path).unwrap(), records);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn compare_with_previous_run() {
+        let records = [
+            record("parse", 100, None),
+            record("part1", 1000, None),
+            record("parse", 100, None),
+            record("part1", 1200, None),
+            record("part2", 500, None),
+        ];
+        let comparisons = compare(&records, None);
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].after.stage, "parse");
+        assert!(!comparisons[0].is_regression(0.1));
+        assert_eq!(comparisons[1].after.stage, "part1");
+        assert!((comparisons[1].change() - 0.2).abs() < 1e-9);
+        assert!(comparisons[1].is_regression(0.1));
+        assert!(!comparisons[1].is_regression(0.25));
+    }
+
+    #[test]
+    fn compare_with_baseline() {
+        let records = [
+            record("part1", 1000, Some("v1")),
+            record("part1", 2000, None),
+            record("part1", 1100, None),
+        ];
+        let comparisons = compare(&records, Some("v1"));
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].before.median_ns, 1000);
+        assert_eq!(comparisons[0].after.median_ns, 1100);
+        assert!(compare(&records, Some("v2")).is_empty());
+    }
+
+    #[test]
+    fn compare_keeps_machines_apart() {
+        let mut other = record("part1", 100, None);
+        other.machine = "laptop".to_string();
+        let records = [record("part1", 1000, None), other];
+        assert!(compare(&records, None).is_empty());
+    }
+}
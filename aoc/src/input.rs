@@ -0,0 +1,396 @@
+use crate::Error;
+use memmap2::Mmap;
+use std::borrow::Cow;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::str;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// Where the input of a run comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Source {
+    /// The day's embedded puzzle input.
+    #[default]
+    Input,
+    /// An embedded example, the default one for the part when not named.
+    Example(Option<String>),
+    /// A file read when the day is run.
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// Reads `--input`, `--example [NAME]`, `--file PATH` or `--stdin` from the
+    /// arguments. Returns `None` when none of them is given.
+    pub fn from_args(args: &[String]) -> Result<Option<Source>, String> {
+        let mut source = None;
+        let mut args = args.iter().peekable();
+        while let Some(arg) = args.next() {
+            let selected = match arg.as_str() {
+                "--input" => Source::Input,
+                "--example" => {
+                    let name = args.next_if(|name| Source::is_example_name(name));
+                    Source::Example(name.cloned())
+                }
+                "--file" => {
+                    let path = args.next().ok_or("--file needs a path")?;
+                    Source::File(PathBuf::from(path))
+                }
+                "--stdin" => Source::Stdin,
+                _ => continue,
+            };
+            if let Some(source) = source {
+                return Err(format!(
+                    "Select one input, not both {source} and {selected}"
+                ));
+            }
+            source = Some(selected);
+        }
+        Ok(source)
+    }
+
+    /// True if the argument after `--example` is the name of the example,
+    /// rather than the next option or a day or part number.
+    pub fn is_example_name(arg: &str) -> bool {
+        !arg.starts_with("--") && !arg.bytes().all(|b| b.is_ascii_digit())
+    }
+
+    /// Name of the input for known answers and benchmark history: `input`,
+    /// the name of the example, `file:` and the path of the file, or `stdin`.
+    /// Files are named so that they cannot be taken for an embedded input.
+    pub fn name(&self) -> String {
+        match self {
+            Source::Input => "input".to_string(),
+            Source::Example(Some(name)) => name.clone(),
+            Source::Example(None) => "example".to_string(),
+            Source::File(path) => format!("file:{}", path.display()),
+            Source::Stdin => "stdin".to_string(),
+        }
+    }
+
+    /// True for the embedded inputs, whose answers can be in `answers.toml`.
+    /// Files and stdin can hold anything, so their answers are never checked.
+    pub fn has_known_answers(&self) -> bool {
+        matches!(self, Source::Input | Source::Example(_))
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Input => write!(f, "puzzle input"),
+            Source::Example(Some(name)) if name.starts_with("example") => write!(f, "{name}"),
+            Source::Example(Some(name)) => write!(f, "example {name}"),
+            Source::Example(None) => write!(f, "default example"),
+            Source::File(path) => write!(f, "file {}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// The text of an input: embedded in the binary, read into memory,
+/// memory-mapped so that large files are not copied onto the heap, or a pipe
+/// on stdin. A mapped file is read through once when it is mapped, to check
+/// that it is UTF-8, and after that its pages can be dropped and paged back
+/// in as needed. A pipe is only read when its text is first needed, and its
+/// lines can be streamed instead without holding all of it in memory.
+pub struct Input {
+    data: Data,
+}
+
+enum Data {
+    Embedded(&'static str),
+    Read(String),
+    /// A mapping already checked to be UTF-8.
+    Mapped(Mmap),
+    /// A pipe, read whole by `try_as_str` unless `stream_lines` streams it
+    /// first.
+    Pipe(Pipe),
+}
+
+struct Pipe {
+    /// The reader, until it is read whole or streamed.
+    reader: Mutex<Option<Box<dyn BufRead + Send>>>,
+    /// The text once it is read, or why it cannot be.
+    text: OnceLock<Result<String, (io::ErrorKind, String)>>,
+}
+
+impl Pipe {
+    fn read_whole(&self) -> io::Result<String> {
+        let mut reader = self.take_reader().ok_or_else(already_streamed)?;
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        Ok(text)
+    }
+
+    fn take_reader(&self) -> Option<Box<dyn BufRead + Send>> {
+        self.reader
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+}
+
+/// Why a pipe that was streamed cannot be read whole.
+fn already_streamed() -> io::Error {
+    io::Error::other("the input was already streamed line by line")
+}
+
+impl Input {
+    /// Memory-maps the file.
+    pub fn map_file(path: impl AsRef<Path>) -> io::Result<Input> {
+        Input::map(File::open(path)?)
+    }
+
+    /// Memory-maps stdin when it is redirected from a file. A pipe is left
+    /// unread until its text or its lines are needed.
+    pub fn stdin() -> io::Result<Input> {
+        #[cfg(unix)]
+        {
+            use std::os::fd::AsFd;
+            let file = File::from(io::stdin().as_fd().try_clone_to_owned()?);
+            if file.metadata()?.is_file() {
+                return Input::map(file);
+            }
+        }
+        Ok(Input::from_reader(BufReader::new(io::stdin())))
+    }
+
+    /// An input read from `reader` like a pipe: whole when its text is first
+    /// needed, or a line at a time by `stream_lines`.
+    pub fn from_reader(reader: impl BufRead + Send + 'static) -> Input {
+        Input {
+            data: Data::Pipe(Pipe {
+                reader: Mutex::new(Some(Box::new(reader))),
+                text: OnceLock::new(),
+            }),
+        }
+    }
+
+    fn map(file: File) -> io::Result<Input> {
+        if file.metadata()?.len() == 0 {
+            return Ok(Input::from(""));
+        }
+        // SAFETY: the mapping is only read, and input files are not expected
+        // to change while a day runs.
+        let map = unsafe { Mmap::map(&file)? };
+        str::from_utf8(&map).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Input {
+            data: Data::Mapped(map),
+        })
+    }
+
+    /// The whole text, reading a pipe into memory the first time.
+    #[track_caller]
+    pub fn as_str(&self) -> &str {
+        match self.try_as_str() {
+            Ok(text) => text,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// The whole text, failing if a pipe cannot be read or its lines were
+    /// already streamed.
+    pub fn try_as_str(&self) -> io::Result<&str> {
+        match &self.data {
+            Data::Embedded(input) => Ok(input),
+            Data::Read(input) => Ok(input),
+            // SAFETY: checked to be UTF-8 when mapped.
+            Data::Mapped(map) => Ok(unsafe { str::from_utf8_unchecked(map) }),
+            Data::Pipe(pipe) => {
+                let text = pipe
+                    .text
+                    .get_or_init(|| pipe.read_whole().map_err(|e| (e.kind(), e.to_string())));
+                text.as_deref()
+                    .map_err(|(kind, message)| io::Error::new(*kind, message.clone()))
+            }
+        }
+    }
+
+    /// Iterates over the lines of the text without copying them, reading a
+    /// pipe into memory whole.
+    ///
+    /// Panics like `as_str` if the text cannot be had.
+    #[track_caller]
+    pub fn lines(&self) -> str::Lines<'_> {
+        self.as_str().lines()
+    }
+
+    /// Iterates over the lines, each failing with its line number if it
+    /// cannot be read. Lines are borrowed from the text when it is in memory
+    /// or mapped. A pipe that was not read whole is streamed a line at a time
+    /// from the first line on, after which its whole text can no longer be
+    /// had: `try_as_str` and streaming it again fail.
+    pub fn stream_lines(&self) -> Lines<'_> {
+        let lines = match &self.data {
+            Data::Pipe(_) => LinesFrom::Pipe(self),
+            _ => match self.try_as_str() {
+                Ok(text) => LinesFrom::Text(text.lines()),
+                Err(e) => LinesFrom::Failed(Some(e)),
+            },
+        };
+        Lines { lines, line: 0 }
+    }
+}
+
+/// The lines of an `Input`, borrowed or streamed.
+pub struct Lines<'a> {
+    lines: LinesFrom<'a>,
+    line: usize,
+}
+
+enum LinesFrom<'a> {
+    Text(str::Lines<'a>),
+    /// A pipe, streamed unless it was read whole before the first line.
+    Pipe(&'a Input),
+    Stream(io::Lines<Box<dyn BufRead + Send>>),
+    Failed(Option<io::Error>),
+}
+
+impl<'a> LinesFrom<'a> {
+    /// Decides how to read a pipe once its first line is wanted: streamed if
+    /// it was not read yet, or from its text if it was.
+    fn start(input: &'a Input) -> LinesFrom<'a> {
+        if let Data::Pipe(pipe) = &input.data {
+            if pipe.text.get().is_none() {
+                if let Some(reader) = pipe.take_reader() {
+                    let e = already_streamed();
+                    let _ = pipe.text.set(Err((e.kind(), e.to_string())));
+                    return LinesFrom::Stream(reader.lines());
+                }
+            }
+        }
+        match input.try_as_str() {
+            Ok(text) => LinesFrom::Text(text.lines()),
+            Err(e) => LinesFrom::Failed(Some(e)),
+        }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Result<Cow<'a, str>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let LinesFrom::Pipe(input) = self.lines {
+            self.lines = LinesFrom::start(input);
+        }
+        self.line += 1;
+        let line = match &mut self.lines {
+            LinesFrom::Text(lines) => Ok(Cow::Borrowed(lines.next()?)),
+            LinesFrom::Stream(lines) => lines.next()?.map(Cow::Owned),
+            LinesFrom::Failed(error) => Err(error.take()?),
+            LinesFrom::Pipe(_) => unreachable!("the pipe was started"),
+        };
+        Some(line.map_err(|e| Error::from(e).at_line(self.line)))
+    }
+}
+
+impl From<&'static str> for Input {
+    fn from(input: &'static str) -> Input {
+        Input {
+            data: Data::Embedded(input),
+        }
+    }
+}
+
+impl From<String> for Input {
+    fn from(input: String) -> Input {
+        Input {
+            data: Data::Read(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn source_from_args() {
+        let source = |a: &[&str]| Source::from_args(&args(a)).unwrap();
+        assert_eq!(source(&["8", "1"]), None);
+        assert_eq!(source(&["8", "--input"]), Some(Source::Input));
+        assert_eq!(
+            source(&["--example", "--bench"]),
+            Some(Source::Example(None))
+        );
+        assert_eq!(
+            source(&["--example", "example1", "8"]),
+            Some(Source::Example(Some("example1".to_string())))
+        );
+        assert_eq!(source(&["--example", "8"]), Some(Source::Example(None)));
+        assert_eq!(
+            source(&["--file", "big.txt"]),
+            Some(Source::File(PathBuf::from("big.txt")))
+        );
+        assert_eq!(source(&["--stdin"]), Some(Source::Stdin));
+    }
+
+    #[test]
+    fn mapped_file_input() {
+        let path = std::env::temp_dir().join(format!("aoc_input_{}.txt", std::process::id()));
+        std::fs::write(&path, "1\n2\n3\n").unwrap();
+        let input = Input::map_file(&path).unwrap();
+        assert_eq!(input.as_str(), "1\n2\n3\n");
+        assert_eq!(input.lines().collect::<Vec<_>>(), ["1", "2", "3"]);
+        let lines = input.stream_lines().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(lines, ["1", "2", "3"]);
+
+        std::fs::write(&path, "").unwrap();
+        assert_eq!(Input::map_file(&path).unwrap().as_str(), "");
+        std::fs::write(&path, [0xff, 0xfe]).unwrap();
+        assert!(Input::map_file(&path).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn names_of_sources() {
+        let file = Source::File(PathBuf::from("input"));
+        assert_eq!(file.name(), "file:input");
+        assert!(!file.has_known_answers());
+        assert!(Source::Input.has_known_answers());
+        let example = |name: &str| Source::Example(Some(name.to_string())).to_string();
+        assert_eq!(example("example"), "example");
+        assert_eq!(example("example2"), "example2");
+        assert_eq!(example("big"), "example big");
+    }
+
+    #[test]
+    fn piped_input() {
+        let pipe = || Input::from_reader(io::Cursor::new("1\n2\n3\n"));
+        let input = pipe();
+        assert_eq!(input.try_as_str().unwrap(), "1\n2\n3\n");
+        let lines = input.stream_lines().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(lines, ["1", "2", "3"]);
+        assert!(matches!(lines[0], Cow::Borrowed(_)));
+
+        let input = pipe();
+        let unused = input.stream_lines();
+        drop(unused);
+        let mut lines = input.stream_lines();
+        assert!(matches!(lines.next(), Some(Ok(Cow::Owned(line))) if line == "1"));
+        assert_eq!(lines.count(), 2);
+        let error = input.try_as_str().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the input was already streamed line by line"
+        );
+        let error = input.stream_lines().next().unwrap().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1: the input was already streamed line by line"
+        );
+    }
+
+    #[test]
+    fn only_one_source() {
+        assert!(Source::from_args(&args(&["--input", "--stdin"])).is_err());
+        assert!(Source::from_args(&args(&["--file"])).is_err());
+    }
+}
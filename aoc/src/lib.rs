@@ -1,122 +1,450 @@
-use log::info;
+use allocations::AllocStats;
+use log::{error, info, warn};
+use logging::TIMING;
 use std::collections::HashSet;
+use std::env;
 use std::fmt::Display;
 use std::fs;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
+use std::process;
 use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+pub mod allocations;
+pub mod answers;
+pub mod benchmark;
+pub mod build;
+pub mod context;
+mod error;
+pub mod geom;
+pub mod grid;
+pub mod history;
+pub mod input;
+pub mod log_files;
+pub mod logging;
+pub mod math;
+pub mod parse;
+pub mod ranges;
+pub mod report;
+pub mod search;
+mod solution;
+pub use answers::{Answers, Verdict};
+pub use benchmark::BenchOptions;
+pub use error::{Error, Result};
+pub use input::{Input, Source};
+pub use logging::LogSpec;
+pub use report::Format;
+pub use solution::{solve, PartRun, Run, Solution, TrySolution};
 
 #[cfg(feature = "log")]
 pub mod log_config;
 
-#[cfg(feature = "log")]
 pub fn init_logging() {
-    log_config::init_logging(log::LevelFilter::Info, log::LevelFilter::Debug)
+    let filename = get_filename();
+    let path = Path::new(&filename);
+    init_logging_as(
+        &path
+            .file_stem()
+            .unwrap_or(path.as_os_str())
+            .to_string_lossy(),
+    );
+}
+
+/// Initializes logging with `name` as the stem of the log file, with the
+/// filters in `AOC_LOG` if it is set.
+pub fn init_logging_as(name: &str) {
+    let spec = LogSpec::from_env().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        None
+    });
+    init_logging_with(name, spec, Format::Text);
+}
+
+/// Initializes logging with `name` as the stem of the log file. Without
+/// filters the terminal shows `info` and the log file `debug`, and with them
+/// both use the filters. With `Format::Json` the terminal log goes to stderr,
+/// leaving stdout to the records of the runs.
+#[cfg(feature = "log")]
+pub fn init_logging_with(name: &str, spec: Option<LogSpec>, format: Format) {
+    let (term, file) = match spec {
+        Some(spec) => (spec.clone(), spec),
+        None => (
+            LogSpec::new(log::LevelFilter::Info),
+            LogSpec::new(log::LevelFilter::Debug),
+        ),
+    };
+    log_config::init_logging(name, term, file, format == Format::Json)
 }
 
 #[cfg(not(feature = "log"))]
 pub mod nolog;
 
 #[cfg(not(feature = "log"))]
-pub fn init_logging() {
-    nolog::init().expect("Logger not initialized");
+pub fn init_logging_with(_name: &str, spec: Option<LogSpec>, format: Format) {
+    nolog::init(
+        spec.unwrap_or(LogSpec::new(log::LevelFilter::Info)),
+        format == Format::Json,
+    )
+    .expect("Logger not initialized");
 }
 
 pub struct Aoc {
-    input: &'static str,
-    example: &'static str,
+    source: Source,
+    name: String,
+    input: Input,
+    part: Option<u8>,
 }
 
 impl Aoc {
-    pub fn init(input: &'static str, example: &'static str) -> Aoc {
-        init_logging();
-        Aoc { input, example }
+    /// Creates a context for an already read input, optionally limited to one part.
+    pub fn new(source: Source, input: impl Into<Input>, part: Option<u8>) -> Aoc {
+        Aoc {
+            name: source.name(),
+            source,
+            input: input.into(),
+            part,
+        }
+    }
+
+    pub fn source(&self) -> &Source {
+        &self.source
     }
 
+    /// Name of the input, like `input` or the name of an example.
+    pub fn input_name(&self) -> &str {
+        &self.name
+    }
+
+    /// The whole input. Panics if stdin cannot be read or was streamed by
+    /// `stream_lines`, where `try_read_input_string` fails instead.
+    #[track_caller]
     pub fn read_input_string(&self) -> &str {
-        self.get_input()
+        self.input.as_str()
+    }
+
+    /// What errors in the input say they are in: the path of a file, or the
+    /// name of the input.
+    pub fn input_file(&self) -> &Path {
+        match &self.source {
+            Source::File(path) => path,
+            _ => Path::new(&self.name),
+        }
+    }
+
+    /// The whole input, failing if stdin cannot be read or was streamed.
+    pub fn try_read_input_string(&self) -> Result<&str> {
+        self.input
+            .try_as_str()
+            .map_err(|e| Error::from(e).in_file(self.input_file()))
     }
 
+    /// The lines of the input, the same whether it is embedded, a mapped file
+    /// or stdin, which is read whole.
+    #[track_caller]
     pub fn input_lines(&self) -> impl Iterator<Item = &str> {
-        self.get_input().lines()
+        self.input.lines()
+    }
+
+    /// The lines of the input, each failing if it cannot be read. Stdin is
+    /// streamed a line at a time unless it was already read whole, and can
+    /// then no longer be read whole: `try_read_input_string` fails.
+    pub fn stream_lines(&self) -> input::Lines<'_> {
+        self.input.stream_lines()
     }
 
-    pub fn read_input_lines(&self) -> Vec<&'static str> {
-        self.get_input().lines().collect()
+    #[track_caller]
+    pub fn read_input_lines(&self) -> Vec<&str> {
+        self.input.lines().collect()
     }
 
-    fn get_input(&self) -> &'static str {
-        use std::env;
-        let input = env::args()
-            .nth(1)
-            .unwrap_or_else(|| "input.txt".to_string());
+    /// Returns true if the given part should be reported.
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
 
-        if input.starts_with("input") {
-            self.input
-        } else {
-            self.example
+/// A day registered with the runner.
+pub struct Day {
+    pub number: u8,
+    pub input: &'static str,
+    /// Named examples, found by `build::embed_examples`.
+    pub examples: &'static [(&'static str, &'static str)],
+    /// Contents of the day's `answers.toml`.
+    pub answers: &'static str,
+    pub solve: fn(&Aoc) -> Run,
+}
+
+impl Day {
+    pub fn example(&self, name: &str) -> Option<&'static str> {
+        self.examples
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, example)| *example)
+    }
+
+    pub fn answers(&self) -> Result<Answers, String> {
+        Answers::parse(self.answers).map_err(|e| format!("Day {}: {e}", self.number))
+    }
+
+    /// The answers to check a run on `aoc`'s input against, none for files
+    /// and stdin.
+    pub fn known_answers(&self, aoc: &Aoc) -> Result<Answers, String> {
+        match aoc.source().has_known_answers() {
+            true => self.answers(),
+            false => Ok(Answers::default()),
         }
     }
+
+    /// Reads the input from `source` and creates the context for running the
+    /// day on it, optionally limited to one part.
+    pub fn load(&self, source: &Source, part: Option<u8>) -> Result<Aoc, String> {
+        match source {
+            Source::Input => Ok(Aoc::new(Source::Input, self.input, part)),
+            Source::Example(name) => self.select_example(name.as_deref(), part),
+            Source::File(path) => {
+                let input = Input::map_file(path)
+                    .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+                Ok(Aoc::new(source.clone(), input, part))
+            }
+            Source::Stdin => {
+                let input = Input::stdin().map_err(|e| format!("Could not read stdin: {e}"))?;
+                Ok(Aoc::new(Source::Stdin, input, part))
+            }
+        }
+    }
+
+    /// Stem of the log files of a run of the day on `aoc`'s input.
+    pub fn log_name(&self, aoc: &Aoc) -> String {
+        match aoc.source() {
+            Source::File(path) => {
+                let stem = path.file_stem().unwrap_or(path.as_os_str());
+                format!("day{:02}_file_{}", self.number, stem.to_string_lossy())
+            }
+            _ => format!("day{:02}_{}", self.number, aoc.input_name()),
+        }
+    }
+
+    /// Creates the context for the named example, or without a name for the
+    /// first example meant for `part`, or for part 1 when no part is given.
+    /// Only the parts the example is meant for are run.
+    pub fn select_example(&self, name: Option<&str>, part: Option<u8>) -> Result<Aoc, String> {
+        let answers = self.answers()?;
+        let (name, example) = match name {
+            Some(name) => self
+                .examples
+                .iter()
+                .find(|(n, _)| *n == name)
+                .ok_or_else(|| format!("Day {} has no example named {name}", self.number))?,
+            None => self
+                .examples
+                .iter()
+                .find(|(n, _)| answers.parts(n).contains(&part.unwrap_or(1)))
+                .or(self.examples.first())
+                .ok_or_else(|| format!("Day {} has no examples", self.number))?,
+        };
+
+        let parts = answers.parts(name);
+        let part = match part {
+            Some(part) if !parts.contains(&part) => {
+                return Err(format!("{name} is not meant for part {part}"));
+            }
+            None if parts.len() == 1 => Some(parts[0]),
+            part => part,
+        };
+        let source = Source::Example(Some(name.to_string()));
+        Ok(Aoc::new(source, *example, part))
+    }
 }
 
+/// Runs the day on the input selected with `--input`, `--example [NAME]`,
+/// `--file PATH` or `--stdin`, the puzzle input by default, benchmarking it
+/// if `--bench` is given, printing a JSON record of the run with
+/// `--format json` and logging with the filters of `--log SPEC`. Exits with
+/// status 1 if the day fails, and 2 if the arguments or the input are bad.
+pub fn run_day(day: &Day) {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let selected = Source::from_args(&args).and_then(|source| {
+        let aoc = day.load(&source.unwrap_or_default(), None)?;
+        let options = BenchOptions::from_args(&args)?;
+        let format = Format::from_args(&args)?;
+        if options.is_some() && format == Format::Json {
+            return Err("--format json does not work with --bench".to_string());
+        }
+        Ok((aoc, options, format, LogSpec::from_args(&args)?))
+    });
+    match selected {
+        Ok((aoc, options, format, log)) => {
+            init_logging_with(&day.log_name(&aoc), log, format);
+            let failed = match (options, format) {
+                (Some(options), _) => benchmark::run(day, &aoc, &options).is_err(),
+                (None, Format::Text) => bench(day, &aoc).failed(),
+                (None, Format::Json) => report::run_json(day, &aoc).failed(),
+            };
+            if failed {
+                process::exit(1);
+            }
+        }
+        Err(message) => {
+            eprintln!("{message}");
+            process::exit(2);
+        }
+    }
+}
+
+/// Runs a closure solving both parts on the puzzle input, or on the example
+/// with `--example`, and logs the answers and the time it took.
+/// ```no_run
+/// # #![allow(deprecated)]
+/// const INPUT: &str = "1\n2\n3\n";
+/// const EXAMPLE: &str = "1\n";
+///
+/// fn main() {
+///     aoc::run_with_bench(INPUT, EXAMPLE, &|aoc: &aoc::Aoc| {
+///         let lines = aoc.read_input_lines();
+///         (lines.len(), lines.join("").len())
+///     });
+/// }
+/// ```
+#[deprecated(note = "implement `Solution` for the day and use `run_day`")]
 pub fn run_with_bench<F, T1, T2>(input: &'static str, example: &'static str, f: &F) -> Duration
 where
     F: Fn(&Aoc) -> (T1, T2),
     T1: Display,
     T2: Display,
 {
-    let aoc = Aoc::init(input, example);
-    let now = ::std::time::Instant::now();
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let aoc = match Source::from_args(&args) {
+        Ok(None | Some(Source::Input)) => Aoc::new(Source::Input, input, None),
+        Ok(Some(Source::Example(_))) => Aoc::new(Source::Example(None), example, None),
+        Ok(Some(source)) => {
+            eprintln!("Only the input and the example can be run without a `Day`, not {source}");
+            process::exit(2);
+        }
+        Err(message) => {
+            eprintln!("{message}");
+            process::exit(2);
+        }
+    };
+    init_logging_as(aoc.input_name());
+    let now = Instant::now();
     let (part1, part2) = f(&aoc);
     let elapsed = now.elapsed();
 
     info!("Part 1: {part1}");
     info!("Part 2: {part2}");
 
-    info!("Time: {:.3?}", elapsed);
+    info!(target: TIMING, "Time: {elapsed:.3?}");
     elapsed
 }
 
-fn benchmark<F, T1, T2>(aoc: &Aoc, f: &F) -> Duration
-where
-    F: Fn(&Aoc) -> (T1, T2),
-    T1: Display,
-    T2: Display,
-{
-    let now = ::std::time::Instant::now();
-    f(aoc);
-    now.elapsed()
+/// Runs the day once and logs the answers and the time each stage took.
+pub fn bench(day: &Day, aoc: &Aoc) -> Run {
+    let run = (day.solve)(aoc);
+    log_run(day, aoc, &run);
+    run
 }
 
-pub fn run_n_times<F, T1, T2>(n: usize, input: &'static str, example: &'static str, f: F)
-where
-    F: Fn(&Aoc) -> (T1, T2),
-    T1: Display,
-    T2: Display,
-{
-    let aoc = Aoc::init(input, example);
-    let mut elapsed = benchmark(&aoc, &f);
-    for _ in 1..n {
-        let dur = benchmark(&aoc, &f);
-        elapsed += dur;
+fn log_run(day: &Day, aoc: &Aoc, run: &Run) {
+    info!(target: TIMING, "Input: {}", aoc.source());
+    match &run.error {
+        Some(e) => error!(target: TIMING, "Parse failed ({:.3?})\n{e:#}", run.parse),
+        None => info!(
+            target: TIMING,
+            "Parse: {:.3?}{}",
+            run.parse,
+            alloc_note(run.parse_allocs)
+        ),
+    }
+    report_answers(day, aoc, run);
+    info!(target: TIMING, "Time: {:.3?}", run.total());
+}
+
+/// Logs the answers, marking them with ✔ or ✘ when the right answer is known.
+fn report_answers(day: &Day, aoc: &Aoc, run: &Run) {
+    let answers = day.known_answers(aoc).unwrap_or_else(|e| {
+        warn!("{e}");
+        Answers::default()
+    });
+    for part in &run.parts {
+        if let Some(e) = &part.error {
+            error!(target: TIMING, "Part {} failed ({:.3?})\n{e:#}", part.part, part.time);
+            continue;
+        }
+        let answer = part.answer.as_deref();
+        let verdict = match answers.check(aoc.input_name(), part.part, answer) {
+            Verdict::Unknown => String::new(),
+            Verdict::Correct => " ✔".to_string(),
+            Verdict::Wrong { expected } => format!(" ✘ expected {expected}"),
+        };
+        info!(
+            target: TIMING,
+            "Part {}: {}{verdict} ({:.3?}{})",
+            part.part,
+            answer.unwrap_or("not solved"),
+            part.time,
+            alloc_note(part.allocs)
+        );
     }
-    info!("Time: {:.3?}", elapsed);
-    info!("Per execution: {:.3?}", elapsed / 1000)
 }
 
+/// What a stage allocated, to follow its time, or nothing when allocations
+/// are not counted.
+fn alloc_note(allocs: Option<AllocStats>) -> String {
+    allocs.map_or(String::new(), |allocs| format!(", {allocs}"))
+}
+
+#[deprecated(note = "use `Day::load` with an input `Source`")]
+#[allow(deprecated)]
+#[track_caller]
 pub fn read_input_lines() -> Vec<String> {
-    read_lines(get_filename())
+    match try_read_input_lines() {
+        Ok(result) => result,
+        Err(e) => panic!("{e}"),
+    }
 }
 
+#[deprecated(note = "use `Day::load` with an input `Source`")]
+pub fn try_read_input_lines() -> Result<Vec<String>> {
+    try_read_lines(get_filename())
+}
+
+#[deprecated(note = "use `Day::load` with an input `Source`")]
+#[allow(deprecated)]
+#[track_caller]
 pub fn read_input_string() -> String {
-    fs::read_to_string(get_filename()).expect("File not found!")
+    match try_read_input_string() {
+        Ok(result) => result,
+        Err(e) => panic!("{e}"),
+    }
 }
 
+#[deprecated(note = "use `Day::load` with an input `Source`")]
+pub fn try_read_input_string() -> Result<String> {
+    let filename = get_filename();
+    fs::read_to_string(&filename).map_err(|e| Error::from(e).in_file(filename))
+}
+
+#[deprecated(note = "use `Day::load` with an input `Source`")]
+#[allow(deprecated)]
+#[track_caller]
 pub fn read_and_split(pattern: &str) -> (String, String) {
-    let input = read_input_string();
-    let a = input.split_once(pattern).expect("Split pattern not found");
-    (a.0.to_owned(), a.1.to_owned())
+    match try_read_and_split(pattern) {
+        Ok(result) => result,
+        Err(e) => panic!("{e}"),
+    }
+}
+
+#[deprecated(note = "use `Day::load` with an input `Source`")]
+#[allow(deprecated)]
+pub fn try_read_and_split(pattern: &str) -> Result<(String, String)> {
+    let input = try_read_input_string()?;
+    let (a, b) = input.split_once(pattern).ok_or_else(|| {
+        Error::new("Split pattern not found")
+            .with_token(pattern)
+            .in_file(get_filename())
+    })?;
+    Ok((a.to_owned(), b.to_owned()))
 }
 
 /// Parse numbers for a list separated with whitespace.
@@ -124,15 +452,37 @@ pub fn read_and_split(pattern: &str) -> (String, String) {
 /// # use aoc::parse_numbers;
 /// assert_eq!(parse_numbers::<u64, Vec<_>>(" 9  40  200"), [9,40,200]);
 /// ```
+#[track_caller]
 pub fn parse_numbers<T, C>(list: &str) -> C
 where
     T: FromStr,
-    <T as FromStr>::Err: core::fmt::Debug,
+    C: std::iter::FromIterator<T>,
+{
+    match try_parse_numbers(list) {
+        Ok(result) => result,
+        Err(e) => panic!("{e}"),
+    }
+}
+
+/// Parse numbers for a list separated with whitespace, failing with the
+/// column of the first token that is not a number.
+/// ```
+/// # use aoc::try_parse_numbers;
+/// assert_eq!(try_parse_numbers::<u64, Vec<_>>(" 9  40  200").unwrap(), [9,40,200]);
+/// let error = try_parse_numbers::<u64, Vec<_>>(" 9  4O  200").unwrap_err();
+/// assert_eq!(error.to_string(), r#"column 5: Not a number: "4O""#);
+/// ```
+pub fn try_parse_numbers<T, C>(list: &str) -> Result<C>
+where
+    T: FromStr,
     C: std::iter::FromIterator<T>,
 {
     list.split_whitespace()
-        .map(|n| n.parse::<T>().expect("Not a number!"))
-        .collect::<C>()
+        .map(|n| {
+            n.parse::<T>()
+                .map_err(|_| Error::new("Not a number").at_token(list, n))
+        })
+        .collect()
 }
 
 /// Parse numbers for a list separated with whitespace into a vector.
@@ -140,58 +490,149 @@ where
 /// # use aoc::parse_numbers_vec;
 /// assert_eq!(parse_numbers_vec::<u64>(" 9  40  200"), [9,40,200]);
 /// ```
-pub fn parse_numbers_vec<T>(list: &str) -> Vec<T>
-where
-    T: FromStr,
-    <T as FromStr>::Err: core::fmt::Debug,
-{
+#[track_caller]
+pub fn parse_numbers_vec<T: FromStr>(list: &str) -> Vec<T> {
     parse_numbers::<T, Vec<_>>(list)
 }
 
+pub fn try_parse_numbers_vec<T: FromStr>(list: &str) -> Result<Vec<T>> {
+    try_parse_numbers::<T, Vec<_>>(list)
+}
+
 /// Parse numbers for a list separated with whitespace into a set.
 /// ```
 /// # use aoc::parse_numbers_set;
 /// # use std::collections::HashSet;
 /// assert_eq!(parse_numbers_set::<u64>(" 9  40  200"),  HashSet::from([9,40,200]));
 /// ```
+#[track_caller]
 pub fn parse_numbers_set<T>(list: &str) -> HashSet<T>
 where
     T: FromStr + std::hash::Hash + std::cmp::Eq,
-    <T as FromStr>::Err: core::fmt::Debug,
 {
     parse_numbers::<T, HashSet<_>>(list)
 }
 
+pub fn try_parse_numbers_set<T>(list: &str) -> Result<HashSet<T>>
+where
+    T: FromStr + std::hash::Hash + std::cmp::Eq,
+{
+    try_parse_numbers::<T, HashSet<_>>(list)
+}
+
+/// Every integer in any text, like `"x=-3, y=10..12"`, parsed into `T`
+/// without allocating. A `-` or `+` right before the digits is the sign,
+/// except after a letter or digit like in `"10-20"`, and for unsigned
+/// types, for which it always separates numbers like in `"1-3"`.
+/// ```
+/// # use aoc::extract_numbers;
+/// assert_eq!(extract_numbers::<i32>("x=-3, y=10..12").collect::<Vec<_>>(), [-3, 10, 12]);
+/// assert_eq!(extract_numbers::<u32>("Card 1: 41 48 | 83").collect::<Vec<_>>(), [1, 41, 48, 83]);
+/// assert_eq!(extract_numbers::<usize>("1,1,3").sum::<usize>(), 5);
+/// ```
+pub fn extract_numbers<T: FromStr>(text: &str) -> impl Iterator<Item = T> + '_ {
+    try_extract_numbers(text).map(|n| match n {
+        Ok(n) => n,
+        Err(e) => panic!("{e}"),
+    })
+}
+
+/// Every integer in any text, failing with the column of a number that
+/// does not fit `T`.
+/// ```
+/// # use aoc::try_extract_numbers;
+/// let numbers = try_extract_numbers::<i32>("p=0,4 v=3,-3 10-20");
+/// assert_eq!(numbers.collect::<aoc::Result<Vec<_>>>().unwrap(), [0, 4, 3, -3, 10, 20]);
+/// let error = try_extract_numbers::<u8>("1-300").find_map(Result::err).unwrap();
+/// assert_eq!(error.to_string(), r#"column 3: Not a number: "300""#);
+/// ```
+pub fn try_extract_numbers<T: FromStr>(text: &str) -> impl Iterator<Item = Result<T>> + '_ {
+    let signed = "-1".parse::<T>().is_ok();
+    parse::integer_tokens(text, signed).map(move |n| {
+        n.parse()
+            .map_err(|_| Error::new("Not a number").at_token(text, n))
+    })
+}
+
+/// Exactly `N` integers from any text, for lines with a fixed number of
+/// them.
+/// ```
+/// # use aoc::extract_array;
+/// let [x, y, dx, dy] = extract_array::<i64, 4>("p=0,4 v=3,-3");
+/// assert_eq!((x, y, dx, dy), (0, 4, 3, -3));
+/// ```
+#[track_caller]
+pub fn extract_array<T: FromStr, const N: usize>(text: &str) -> [T; N] {
+    match try_extract_array(text) {
+        Ok(result) => result,
+        Err(e) => panic!("{e}"),
+    }
+}
+
+/// Exactly `N` integers from any text, failing if there are more or fewer.
+/// ```
+/// # use aoc::try_extract_array;
+/// let error = try_extract_array::<u32, 3>("50 98").unwrap_err();
+/// assert_eq!(error.to_string(), r#"Expected 3 numbers, found 2: "50 98""#);
+/// ```
+pub fn try_extract_array<T: FromStr, const N: usize>(text: &str) -> Result<[T; N]> {
+    let mut numbers = try_extract_numbers(text);
+    let mut error = None;
+    let array: [Option<T>; N] = std::array::from_fn(|_| match numbers.next()? {
+        Ok(n) => Some(n),
+        Err(e) => {
+            error.get_or_insert(e);
+            None
+        }
+    });
+    if let Some(error) = error {
+        return Err(error);
+    }
+    let found = array.iter().flatten().count() + numbers.count();
+    if found != N {
+        return Err(Error::new(format!("Expected {N} numbers, found {found}")).with_token(text));
+    }
+    Ok(array.map(|n| n.expect("all numbers were found")))
+}
+
 fn get_filename() -> String {
-    use std::env;
     env::args()
         .nth(1)
         .unwrap_or_else(|| "input.txt".to_string())
 }
 
+#[deprecated(note = "use `Day::load` with an input `Source`")]
+#[allow(deprecated)]
 pub fn input_lines() -> impl Iterator<Item = String> {
-    iter_lines(get_filename())
+    try_input_lines()
+        .unwrap_or_else(|e| panic!("{e}"))
+        .map(|line| line.unwrap_or_else(|e| panic!("{e}")))
 }
 
-fn iter_lines<P>(filename: P) -> impl Iterator<Item = String>
+#[deprecated(note = "use `Day::load` with an input `Source`")]
+pub fn try_input_lines() -> Result<impl Iterator<Item = Result<String>>> {
+    try_iter_lines(get_filename())
+}
+
+/// Opens the file and iterates over its lines, each failing with its line
+/// number if it cannot be read.
+fn try_iter_lines<P>(filename: P) -> Result<impl Iterator<Item = Result<String>>>
 where
     P: AsRef<Path>,
 {
-    let file = fs::File::open(filename).expect("File not found!");
-    BufReader::new(file)
+    let path = filename.as_ref().to_path_buf();
+    let file = fs::File::open(&path).map_err(|e| Error::from(e).in_file(&path))?;
+    Ok(BufReader::new(file)
         .lines()
-        .map(|l| l.expect("Error reading line"))
+        .enumerate()
+        .map(move |(i, line)| line.map_err(|e| Error::from(e).in_file(&path).at_line(i + 1))))
 }
 
-fn read_lines<P>(filename: P) -> Vec<String>
+fn try_read_lines<P>(filename: P) -> Result<Vec<String>>
 where
     P: AsRef<Path>,
 {
-    let file = fs::File::open(filename).expect("File not found!");
-    BufReader::new(file)
-        .lines()
-        .map(|l| l.expect("Error reading line"))
-        .collect()
+    try_iter_lines(filename)?.collect()
 }
 
 #[cfg(test)]
@@ -200,7 +641,13 @@ mod tests {
 
     #[test]
     fn read_lines_works() {
-        let result = read_lines("example.txt");
+        let result = try_read_lines("example.txt").unwrap();
         assert_eq!(result, vec!["1", "2", "3"]);
     }
+
+    #[test]
+    fn missing_file_is_named() {
+        let error = try_read_lines("missing.txt").unwrap_err();
+        assert_eq!(error.file.as_deref(), Some(Path::new("missing.txt")));
+    }
 }
@@ -1,29 +1,141 @@
-use super::get_filename;
+use crate::log_files::{self, LogFile, Retention, LOG_DIR};
+use crate::logging::LogSpec;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use log::{warn, Log, Metadata, Record};
 use simplelog::*;
 use std::fs;
+use std::io;
 use std::path::Path;
+use std::time::SystemTime;
 
 pub use simplelog::LevelFilter;
 
-pub fn init_logging(term: LevelFilter, file: LevelFilter) {
-    fs::create_dir_all("target/log").expect("Cannot create log directory");
-    let filename = get_filename();
-    let filename = filename.split_once('.').unwrap().0;
-    let mut i = 1;
-    while Path::new(&format!("target/log/{filename}_{i}.txt")).exists() {
-        i += 1;
-    }
-    let filename = format!("target/log/{filename}_{i}.txt");
+/// Logs to the terminal with the `term` filters and to a new file under
+/// `target/log` with the `file` filters. The terminal log goes to stderr if
+/// `stderr` is set, and otherwise only errors do. Older logs of the same
+/// `filename` are then retired as `log_files` describes.
+pub fn init_logging(filename: &str, term: LogSpec, file: LogSpec, stderr: bool) {
+    let retention = Retention::from_env().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        Retention::default()
+    });
+    let dir = Path::new(LOG_DIR);
+    fs::create_dir_all(dir).expect("Cannot create log directory");
+    let earlier = log_files::log_files(dir, filename).unwrap_or_else(|e| {
+        eprintln!("Cannot list the logs in {LOG_DIR}: {e}");
+        vec![]
+    });
+    let path = log_files::next_path(dir, filename, &earlier);
     let config = ConfigBuilder::new()
         .set_time_offset_to_local()
         .expect("Local timezone not found")
         .build();
 
     CombinedLogger::init(vec![
-        TermLogger::new(term, config.clone(), TerminalMode::Mixed, ColorChoice::Auto),
-        WriteLogger::new(file, config, fs::File::create(&filename).unwrap()),
+        Filtered::new(
+            TermLogger::new(
+                term.max_level(),
+                config.clone(),
+                if stderr {
+                    TerminalMode::Stderr
+                } else {
+                    TerminalMode::Mixed
+                },
+                ColorChoice::Auto,
+            ),
+            term,
+        ),
+        Filtered::new(
+            WriteLogger::new(file.max_level(), config, fs::File::create(&path).unwrap()),
+            file,
+        ),
     ])
     .unwrap();
 
-    println!("Logs outputting to {filename}")
+    if stderr {
+        eprintln!("Logs outputting to {}", path.display())
+    } else {
+        println!("Logs outputting to {}", path.display())
+    }
+    retire(filename, &path, &earlier, retention);
+}
+
+/// Deletes the earlier logs past the retention, compresses the others if
+/// `AOC_LOG_GZIP` is set and links the new log as the latest.
+fn retire(filename: &str, path: &Path, earlier: &[LogFile], retention: Retention) {
+    let expired = log_files::expired(earlier, retention, SystemTime::now());
+    for file in &expired {
+        if let Err(e) = fs::remove_file(&file.path) {
+            warn!("Cannot delete {}: {e}", file.path.display());
+        }
+    }
+    if log_files::gzip_from_env() {
+        let kept = earlier.iter().filter(|file| !expired.contains(file));
+        for file in kept.filter(|file| !file.compressed) {
+            if let Err(e) = gzip(&file.path) {
+                warn!("Cannot compress {}: {e}", file.path.display());
+            }
+        }
+    }
+    let dir = Path::new(LOG_DIR);
+    for link in [format!("{filename}_latest.txt"), "latest.txt".to_string()] {
+        if let Err(e) = log_files::link_latest(path, &dir.join(&link)) {
+            warn!("Cannot link {link} to the new log: {e}");
+        }
+    }
+}
+
+/// Replaces the file with a `.gz` of it, keeping its modification time for
+/// the retention.
+fn gzip(path: &Path) -> io::Result<()> {
+    let mut compressed = path.as_os_str().to_owned();
+    compressed.push(".gz");
+    let modified = fs::metadata(path)?.modified()?;
+    let mut encoder = GzEncoder::new(fs::File::create(&compressed)?, Compression::default());
+    io::copy(&mut fs::File::open(path)?, &mut encoder)?;
+    encoder.finish()?.set_modified(modified)?;
+    fs::remove_file(path)
+}
+
+/// A logger that only passes on the records its filters let through.
+struct Filtered {
+    logger: Box<dyn SharedLogger>,
+    spec: LogSpec,
+}
+
+impl Filtered {
+    fn new(logger: Box<dyn SharedLogger>, spec: LogSpec) -> Box<Filtered> {
+        Box::new(Filtered { logger, spec })
+    }
+}
+
+impl Log for Filtered {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.spec.enabled(metadata) && self.logger.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.spec.enabled(record.metadata()) {
+            self.logger.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.logger.flush();
+    }
+}
+
+impl SharedLogger for Filtered {
+    fn level(&self) -> LevelFilter {
+        self.spec.max_level()
+    }
+
+    fn config(&self) -> Option<&Config> {
+        self.logger.config()
+    }
+
+    fn as_log(self: Box<Self>) -> Box<dyn Log> {
+        self
+    }
 }
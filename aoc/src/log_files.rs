@@ -0,0 +1,278 @@
+//! Naming and retention of the log files under `target/log`.
+//!
+//! Each run logs to `<name>_<i>.txt`, `i` counting up from the last log of
+//! the same day and input, and `<name>_latest.txt` links to it. Older logs
+//! are deleted past the retention in `AOC_LOG_KEEP`, and compressed to
+//! `<name>_<i>.txt.gz` when `AOC_LOG_GZIP` is set.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+pub const LOG_DIR: &str = "target/log";
+
+/// The environment variable with the retention, like `20` or `7d`.
+pub const KEEP_VAR: &str = "AOC_LOG_KEEP";
+
+/// The environment variable that turns on compressing older logs.
+pub const GZIP_VAR: &str = "AOC_LOG_GZIP";
+
+/// Which logs of a day and input are kept, counting the new one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Retention {
+    /// The last `N` logs.
+    Last(usize),
+    /// The logs written in the last `N` days.
+    Days(u64),
+    All,
+}
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+impl Default for Retention {
+    fn default() -> Retention {
+        Retention::Last(20)
+    }
+}
+
+impl Retention {
+    /// The retention in `AOC_LOG_KEEP`, the last 20 logs if it is not set.
+    pub fn from_env() -> Result<Retention, String> {
+        match env::var(KEEP_VAR) {
+            Ok(keep) if !keep.trim().is_empty() => {
+                keep.trim().parse().map_err(|e| format!("{KEEP_VAR}: {e}"))
+            }
+            _ => Ok(Retention::default()),
+        }
+    }
+}
+
+impl FromStr for Retention {
+    type Err = String;
+
+    /// Parses `N` for the last `N` logs, `Nd` for the last `N` days or `all`.
+    fn from_str(keep: &str) -> Result<Retention, String> {
+        let invalid = || format!("Not a number of logs or days: {keep:?}");
+        if keep == "all" {
+            return Ok(Retention::All);
+        }
+        match keep.strip_suffix('d') {
+            Some(days) => days
+                .parse::<u64>()
+                .ok()
+                .filter(|&days| days > 0 && days.checked_mul(SECS_PER_DAY).is_some())
+                .map(Retention::Days),
+            None => keep.parse().ok().filter(|&n| n > 0).map(Retention::Last),
+        }
+        .ok_or_else(invalid)
+    }
+}
+
+/// True if `AOC_LOG_GZIP` asks for older logs to be compressed.
+pub fn gzip_from_env() -> bool {
+    env::var(GZIP_VAR).is_ok_and(|gzip| !matches!(gzip.trim(), "" | "0" | "false" | "no"))
+}
+
+/// A log of an earlier run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFile {
+    pub path: PathBuf,
+    pub index: usize,
+    pub compressed: bool,
+    pub modified: SystemTime,
+}
+
+/// The logs named `<name>_<i>.txt` or `<name>_<i>.txt.gz` in `dir`, in the
+/// order they were written. A missing directory has none.
+pub fn log_files(dir: impl AsRef<Path>, name: &str) -> io::Result<Vec<LogFile>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+    let mut files = vec![];
+    for entry in entries {
+        let entry = entry?;
+        let Some((index, compressed)) = parse_file_name(&entry.file_name().to_string_lossy(), name)
+        else {
+            continue;
+        };
+        files.push(LogFile {
+            path: entry.path(),
+            index,
+            compressed,
+            modified: entry.metadata()?.modified()?,
+        });
+    }
+    files.sort_by_key(|file| file.index);
+    Ok(files)
+}
+
+/// The index and whether it is compressed, if `file_name` is a log of `name`.
+fn parse_file_name(file_name: &str, name: &str) -> Option<(usize, bool)> {
+    let rest = file_name.strip_prefix(name)?.strip_prefix('_')?;
+    let (index, compressed) = match rest.strip_suffix(".txt.gz") {
+        Some(index) => (index, true),
+        None => (rest.strip_suffix(".txt")?, false),
+    };
+    if !index.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((index.parse().ok()?, compressed))
+}
+
+/// The path of the next log of `name`, after the last of `files`.
+pub fn next_path(dir: impl AsRef<Path>, name: &str, files: &[LogFile]) -> PathBuf {
+    let index = files.last().map_or(1, |file| file.index + 1);
+    dir.as_ref().join(format!("{name}_{index}.txt"))
+}
+
+/// The earlier logs to delete so that, with the new log, only those
+/// `retention` keeps are left.
+pub fn expired(files: &[LogFile], retention: Retention, now: SystemTime) -> Vec<&LogFile> {
+    match retention {
+        Retention::Last(n) => files[..files.len().saturating_sub(n.saturating_sub(1))]
+            .iter()
+            .collect(),
+        Retention::Days(days) => {
+            let age = Duration::from_secs(days.saturating_mul(SECS_PER_DAY));
+            files
+                .iter()
+                .filter(|file| {
+                    now.duration_since(file.modified)
+                        .is_ok_and(|since| since > age)
+                })
+                .collect()
+        }
+        Retention::All => vec![],
+    }
+}
+
+/// Points `link` at `target`, replacing what `link` pointed at before. Only
+/// supported on Unix, elsewhere there are no links.
+pub fn link_latest(target: &Path, link: &Path) -> io::Result<()> {
+    match fs::remove_file(link) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    #[cfg(unix)]
+    {
+        // Relative to the directory of the link, so that it survives moving
+        // the directory.
+        let target = target.file_name().map_or(target, Path::new);
+        std::os::unix::fs::symlink(target, link)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = target;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(index: usize, days_old: u64) -> LogFile {
+        LogFile {
+            path: PathBuf::from(format!("day10_input_{index}.txt")),
+            index,
+            compressed: false,
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs((100 - days_old) * 86400),
+        }
+    }
+
+    #[test]
+    fn parses_retention() {
+        assert_eq!("5".parse(), Ok(Retention::Last(5)));
+        assert_eq!("7d".parse(), Ok(Retention::Days(7)));
+        assert_eq!("all".parse(), Ok(Retention::All));
+        assert!("0".parse::<Retention>().is_err());
+        assert!("d".parse::<Retention>().is_err());
+        assert!("1w".parse::<Retention>().is_err());
+        assert!(format!("{}d", u64::MAX / 1000)
+            .parse::<Retention>()
+            .is_err());
+    }
+
+    #[test]
+    fn names_of_logs() {
+        assert_eq!(
+            parse_file_name("day10_input_3.txt", "day10_input"),
+            Some((3, false))
+        );
+        assert_eq!(
+            parse_file_name("day10_input_12.txt.gz", "day10_input"),
+            Some((12, true))
+        );
+        assert_eq!(
+            parse_file_name("day10_input_latest.txt", "day10_input"),
+            None
+        );
+        assert_eq!(parse_file_name("day10_input_2_1.txt", "day10_input"), None);
+        assert_eq!(parse_file_name("day10_input_+1.txt", "day10_input"), None);
+        assert_eq!(parse_file_name("day10_example_1.txt", "day10_input"), None);
+        assert_eq!(parse_file_name("day10_1.txt", "day10"), Some((1, false)));
+    }
+
+    #[test]
+    fn expires_old_logs() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(100 * 86400);
+        let files = [file(2, 30), file(5, 8), file(6, 6), file(9, 0)];
+        let indices = |retention| {
+            expired(&files, retention, now)
+                .iter()
+                .map(|file| file.index)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(indices(Retention::Last(3)), [2, 5]);
+        assert_eq!(indices(Retention::Last(1)), [2, 5, 6, 9]);
+        assert!(indices(Retention::Last(10)).is_empty());
+        assert_eq!(indices(Retention::Days(7)), [2, 5]);
+        assert!(indices(Retention::All).is_empty());
+        assert_eq!(
+            next_path(LOG_DIR, "day10_input", &files),
+            Path::new("target/log/day10_input_10.txt")
+        );
+        assert_eq!(
+            next_path(LOG_DIR, "day10_input", &[]),
+            Path::new("target/log/day10_input_1.txt")
+        );
+    }
+
+    #[test]
+    fn lists_logs_in_order() {
+        let dir = env::temp_dir().join(format!("aoc_logs_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        assert!(log_files(&dir, "day01_input").unwrap().is_empty());
+        fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "day01_input_10.txt",
+            "day01_input_9.txt.gz",
+            "day01_example_1.txt",
+        ] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        let files = log_files(&dir, "day01_input").unwrap();
+        let found = files
+            .iter()
+            .map(|file| (file.index, file.compressed))
+            .collect::<Vec<_>>();
+        assert_eq!(found, [(9, true), (10, false)]);
+
+        #[cfg(unix)]
+        {
+            let link = dir.join("day01_input_latest.txt");
+            link_latest(&files[1].path, &link).unwrap();
+            link_latest(&files[1].path, &link).unwrap();
+            assert_eq!(
+                fs::read_link(&link).unwrap(),
+                Path::new("day01_input_10.txt")
+            );
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,164 @@
+//! Log filters chosen at run time, like `info,day10=trace,aoc::search=debug`.
+
+use log::{LevelFilter, Metadata};
+use std::env;
+use std::str::FromStr;
+
+/// Target of the answers and timings of a run, kept apart from the tracing
+/// of the solutions so that turning that down does not hide them.
+pub const TIMING: &str = "timing";
+
+/// The environment variable read when `--log` is not given.
+pub const ENV_VAR: &str = "AOC_LOG";
+
+/// A default level and levels for targets, which cover their submodules.
+/// The `timing` target stays at `info` unless it is named.
+///
+/// ```
+/// # use aoc::logging::LogSpec;
+/// # use log::LevelFilter;
+/// let spec: LogSpec = "warn,day10=trace".parse().unwrap();
+/// assert_eq!(spec.level_for("day10::pipes"), LevelFilter::Trace);
+/// assert_eq!(spec.level_for("day100"), LevelFilter::Warn);
+/// assert_eq!(spec.level_for("timing"), LevelFilter::Info);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogSpec {
+    pub default: LevelFilter,
+    pub targets: Vec<(String, LevelFilter)>,
+}
+
+impl LogSpec {
+    pub fn new(default: LevelFilter) -> LogSpec {
+        LogSpec {
+            default,
+            targets: vec![],
+        }
+    }
+
+    /// The filters given with `--log SPEC`, or else in `AOC_LOG`.
+    pub fn from_args(args: &[String]) -> Result<Option<LogSpec>, String> {
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--log" {
+                let spec = args
+                    .next()
+                    .ok_or("--log needs a filter like info,day10=trace")?;
+                return spec.parse().map(Some);
+            }
+        }
+        LogSpec::from_env()
+    }
+
+    /// The filters in `AOC_LOG`, if it is set.
+    pub fn from_env() -> Result<Option<LogSpec>, String> {
+        match env::var(ENV_VAR) {
+            Ok(spec) if !spec.trim().is_empty() => spec
+                .parse()
+                .map(Some)
+                .map_err(|e| format!("{ENV_VAR}: {e}")),
+            _ => Ok(None),
+        }
+    }
+
+    /// The level of the longest target that is `target` or one of its
+    /// parent modules.
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        let named = self
+            .targets
+            .iter()
+            .filter(|(name, _)| {
+                target
+                    .strip_prefix(name.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(name, _)| name.len());
+        match named {
+            Some((_, level)) => *level,
+            None if target == TIMING => self.default.max(LevelFilter::Info),
+            None => self.default,
+        }
+    }
+
+    pub fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_for(metadata.target())
+    }
+
+    /// The most verbose level of any target.
+    pub fn max_level(&self) -> LevelFilter {
+        self.targets
+            .iter()
+            .map(|(_, level)| *level)
+            .chain([self.level_for(TIMING)])
+            .fold(self.default, Ord::max)
+    }
+}
+
+impl FromStr for LogSpec {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<LogSpec, String> {
+        let mut parsed = LogSpec::new(LevelFilter::Info);
+        for item in spec
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+        {
+            let level = |level: &str| {
+                level
+                    .trim()
+                    .parse::<LevelFilter>()
+                    .map_err(|_| format!("Unknown log level {level:?}"))
+            };
+            match item.split_once('=') {
+                Some((target, level_name)) => {
+                    let target = target.trim().replace('-', "_");
+                    parsed.targets.push((target, level(level_name)?));
+                }
+                None => parsed.default = level(item)?,
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_filters() {
+        let spec: LogSpec = "debug, day10=trace, aoc::search=off".parse().unwrap();
+        assert_eq!(spec.default, LevelFilter::Debug);
+        assert_eq!(spec.level_for("day10"), LevelFilter::Trace);
+        assert_eq!(spec.level_for("aoc::search"), LevelFilter::Off);
+        assert_eq!(spec.level_for("aoc::searching"), LevelFilter::Debug);
+        assert_eq!(spec.max_level(), LevelFilter::Trace);
+        assert_eq!(
+            "day10=loud".parse::<LogSpec>().unwrap_err(),
+            "Unknown log level \"loud\""
+        );
+        assert_eq!(
+            "".parse::<LogSpec>().unwrap(),
+            LogSpec::new(LevelFilter::Info)
+        );
+    }
+
+    #[test]
+    fn timing_is_separate() {
+        let spec: LogSpec = "off".parse().unwrap();
+        assert_eq!(spec.level_for(TIMING), LevelFilter::Info);
+        assert_eq!(spec.level_for("day10"), LevelFilter::Off);
+        assert_eq!(spec.max_level(), LevelFilter::Info);
+        let spec: LogSpec = "off,timing=off".parse().unwrap();
+        assert_eq!(spec.max_level(), LevelFilter::Off);
+    }
+
+    #[test]
+    fn log_option_wins_over_env() {
+        let args = ["7", "--log", "day07=debug"].map(String::from);
+        let spec = LogSpec::from_args(&args).unwrap().unwrap();
+        assert_eq!(spec.level_for("day07"), LevelFilter::Debug);
+        assert!(LogSpec::from_args(&["--log".to_string()]).is_err());
+    }
+}
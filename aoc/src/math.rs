@@ -0,0 +1,182 @@
+//! Number theory, mostly for working out when cycles line up.
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, which panics on overflow, where
+/// `checked_lcm` gives `None`.
+#[track_caller]
+pub fn lcm(a: u64, b: u64) -> u64 {
+    checked_lcm(a, b).expect("lcm overflows u64")
+}
+
+pub fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The greatest common divisor of all the numbers, 0 when there are none.
+pub fn gcd_all(numbers: impl IntoIterator<Item = u64>) -> u64 {
+    numbers.into_iter().fold(0, gcd)
+}
+
+/// The least common multiple of all the numbers, 1 when there are none.
+/// Panics on overflow, where `checked_lcm_all` gives `None`.
+#[track_caller]
+pub fn lcm_all(numbers: impl IntoIterator<Item = u64>) -> u64 {
+    numbers.into_iter().fold(1, lcm)
+}
+
+pub fn checked_lcm_all(numbers: impl IntoIterator<Item = u64>) -> Option<u64> {
+    numbers.into_iter().try_fold(1, checked_lcm)
+}
+
+/// The greatest common divisor `g` of `a` and `b`, and `x` and `y` such that
+/// `a * x + b * y == g`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `base` to the power `exp`, modulo `modulus`. Panics if `modulus` is 0,
+/// where `checked_mod_pow` gives `None`.
+#[track_caller]
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    checked_mod_pow(base, exp, modulus).expect("mod_pow with a modulus of 0")
+}
+
+/// `base` to the power `exp`, modulo `modulus`, or `None` if `modulus` is 0.
+pub fn checked_mod_pow(base: u64, mut exp: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    Some(result as u64)
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1 (mod modulus)`, if `a` and
+/// `modulus` are coprime.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    if modulus <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves the congruences `x ≡ remainder (mod modulus)` for each
+/// `(remainder, modulus)`, giving the smallest `x` that is not negative and
+/// the modulus all solutions share, which is the lcm of the moduli. The
+/// moduli do not need to be coprime. `None` if there is no solution, a
+/// modulus is not positive, or the lcm overflows.
+///
+/// ```
+/// # use aoc::math::crt;
+/// assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt([(3, 4), (1, 6)]), Some((7, 12)));
+/// assert_eq!(crt([(0, 4), (1, 6)]), None);
+/// ```
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1): (i64, i64), (r2, m2)| {
+            if m2 <= 0 {
+                return None;
+            }
+            let r2 = r2.rem_euclid(m2);
+            let (g, p, _) = extended_gcd(m1, m2);
+            if (r2 - r1) % g != 0 {
+                return None;
+            }
+            let lcm = (m1 / g).checked_mul(m2)?;
+            // x = r1 + m1 * k, where m1 * k ≡ r2 - r1 (mod m2).
+            let k = ((r2 - r1) / g) as i128 * p as i128 % (m2 / g) as i128;
+            let x = (r1 as i128 + m1 as i128 * k).rem_euclid(lcm as i128);
+            Some((x as i64, lcm))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(gcd_all([]), 0);
+        assert_eq!(lcm_all([2, 3, 4, 5]), 60);
+        assert_eq!(lcm_all([]), 1);
+        assert_eq!(checked_lcm_all([u64::MAX, 2]), None);
+        assert_eq!(checked_lcm_all([u64::MAX, 3]), Some(u64::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "lcm overflows u64")]
+    fn lcm_panics_on_overflow() {
+        lcm_all([u64::MAX, 2]);
+    }
+
+    #[test]
+    fn extended_euclid() {
+        for (a, b) in [(240, 46), (-240, 46), (46, -240), (7, 0), (0, 7)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i64);
+            assert_eq!(a * x + b * y, g, "{a} {b}");
+        }
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, 1_000_000_007), 254_368_884);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(checked_mod_pow(4, 13, 497), Some(445));
+        assert_eq!(checked_mod_pow(4, 13, 0), None);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([(0, 12), (0, 18)]), Some((0, 36)));
+        assert_eq!(crt([(5, 0)]), None);
+        let moduli = [1_000_003, 999_983, 10_007];
+        let (x, m) = crt(moduli.map(|m| (123_456, m))).unwrap();
+        assert_eq!((x, m), (123_456, moduli.iter().product()));
+        assert_eq!(crt([(0, i64::MAX), (1, i64::MAX - 1)]), None);
+    }
+}
@@ -1,23 +1,36 @@
+use crate::logging::LogSpec;
 use log::SetLoggerError;
-use log::{LevelFilter, Metadata, Record};
+use log::{Metadata, Record};
 
-struct NoLogging;
+/// Prints the records its filters let through, without timestamps or a log
+/// file.
+struct NoLogging {
+    spec: LogSpec,
+    stderr: bool,
+}
 
 impl log::Log for NoLogging {
-    fn enabled(&self, _metadata: &Metadata) -> bool {
-        dbg!(_metadata);
-        true
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.spec.enabled(metadata)
     }
 
     fn log(&self, record: &Record) {
-        println!("{}", record.args());
+        if self.enabled(record.metadata()) {
+            if self.stderr {
+                eprintln!("{}", record.args());
+            } else {
+                println!("{}", record.args());
+            }
+        }
     }
 
     fn flush(&self) {}
 }
 
-static LOGGER: NoLogging = NoLogging;
-
-pub fn init() -> Result<(), SetLoggerError> {
-    log::set_logger(&LOGGER).map(|()| log::set_max_level(LevelFilter::Info))
+/// Logs to stdout, or to stderr if `stderr` is set.
+pub fn init(spec: LogSpec, stderr: bool) -> Result<(), SetLoggerError> {
+    let max_level = spec.max_level();
+    // The logger lives as long as the program, like one in a static.
+    let logger = Box::leak(Box::new(NoLogging { spec, stderr }));
+    log::set_logger(logger).map(|()| log::set_max_level(max_level))
 }
@@ -0,0 +1,256 @@
+//! Splitting and scanning the shapes of input that come up every year.
+//! Errors point at the column of the text that did not fit.
+
+use crate::error::{Error, Result};
+use std::any::type_name;
+use std::str::FromStr;
+
+/// The blocks of lines separated by blank lines, without the newline at
+/// their end.
+///
+/// ```
+/// # use aoc::parse::blocks;
+/// let input = "seeds: 79 14\n\nsoil map:\n50 98 2\n52 50 48\n";
+/// assert_eq!(blocks(input).collect::<Vec<_>>(), ["seeds: 79 14", "soil map:\n50 98 2\n52 50 48"]);
+/// ```
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+    std::iter::from_fn(move || {
+        rest = rest.trim_start_matches(['\n', '\r']);
+        while let Some(end) = rest.find('\n').filter(|end| rest[..*end].trim().is_empty()) {
+            rest = rest[end..].trim_start_matches(['\n', '\r']);
+        }
+        if rest.trim().is_empty() {
+            return None;
+        }
+        let mut end = 0;
+        while end < rest.len() {
+            let line_end = rest[end..].find('\n').map_or(rest.len(), |i| end + i + 1);
+            if rest[end..line_end].trim().is_empty() {
+                break;
+            }
+            end = line_end;
+        }
+        let block = rest[..end].trim_end_matches(['\n', '\r']);
+        rest = &rest[end..];
+        Some(block)
+    })
+}
+
+/// The first line of a block without a `:` at its end, and the rest of the
+/// block.
+///
+/// ```
+/// # use aoc::parse::header;
+/// assert_eq!(header("soil map:\n50 98 2\n52 50 48"), ("soil map", "50 98 2\n52 50 48"));
+/// ```
+pub fn header(block: &str) -> (&str, &str) {
+    let (first, rest) = block.split_once('\n').unwrap_or((block, ""));
+    (first.trim_end().trim_end_matches(':'), rest)
+}
+
+/// The key and the value of a `key: value` line, trimmed.
+///
+/// ```
+/// # use aoc::parse::key_value;
+/// assert_eq!(key_value("Game 12: 3 blue").unwrap(), ("Game 12", "3 blue"));
+/// assert!(key_value("Game 12").is_err());
+/// ```
+pub fn key_value(line: &str) -> Result<(&str, &str)> {
+    let (key, value) = line
+        .split_once(':')
+        .ok_or_else(|| Error::new("Expected \"key: value\"").with_token(line))?;
+    Ok((key.trim(), value.trim()))
+}
+
+/// The label and the numbers of a line like `Time:  7  15  30`.
+///
+/// ```
+/// # use aoc::parse::labelled_numbers;
+/// assert_eq!(labelled_numbers::<u32>("Time:  7  15  30").unwrap(), ("Time", vec![7, 15, 30]));
+/// let error = labelled_numbers::<u32>("Time:  7  1S  30").unwrap_err();
+/// assert_eq!(error.to_string(), r#"column 11: Not a number: "1S""#);
+/// ```
+pub fn labelled_numbers<T: FromStr>(line: &str) -> Result<(&str, Vec<T>)> {
+    let (label, numbers) = key_value(line)?;
+    let numbers = numbers
+        .split_whitespace()
+        .map(|n| {
+            n.parse()
+                .map_err(|_| Error::new("Not a number").at_token(line, n))
+        })
+        .collect::<Result<_>>()?;
+    Ok((label, numbers))
+}
+
+/// The slices of `text` that are integers. When `signed`, a `-` or `+`
+/// right before the digits is part of the number, unless it follows a
+/// letter or digit like in `"10-20"`, where it separates two numbers.
+pub(crate) fn integer_tokens(text: &str, signed: bool) -> impl Iterator<Item = &str> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        let digit = i + bytes[i..].iter().position(u8::is_ascii_digit)?;
+        let start = match digit.checked_sub(1).map(|sign| bytes[sign]) {
+            Some(b'-' | b'+') if signed && digit > i && !follows_word(text, digit - 1) => digit - 1,
+            _ => digit,
+        };
+        i = digit
+            + bytes[digit..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+        Some(&text[start..i])
+    })
+}
+
+/// Whether the character before `at` is a letter or digit.
+fn follows_word(text: &str, at: usize) -> bool {
+    text[..at]
+        .chars()
+        .next_back()
+        .is_some_and(char::is_alphanumeric)
+}
+
+/// A value `scan!` can read from the text of a field.
+pub trait Field<'a>: Sized {
+    fn from_field(text: &'a str) -> Option<Self>;
+}
+
+impl<'a> Field<'a> for &'a str {
+    fn from_field(text: &'a str) -> Option<&'a str> {
+        Some(text)
+    }
+}
+
+macro_rules! from_str_fields {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> Field<'a> for $t {
+                fn from_field(text: &'a str) -> Option<$t> {
+                    text.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+from_str_fields!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, char, bool, String
+);
+
+/// The fields of `input` where `pattern` has `{}`, with the text between
+/// them matching exactly. A field ends where the text after it is first
+/// found. Used by `scan!`.
+pub fn scan_fields<'a>(input: &'a str, pattern: &str, count: usize) -> Result<Vec<&'a str>> {
+    let mut literals = pattern.split("{}");
+    let first = literals.next().unwrap_or_default();
+    let literals = literals.collect::<Vec<_>>();
+    assert_eq!(
+        literals.len(),
+        count,
+        "{pattern:?} does not have {count} fields"
+    );
+
+    let expected =
+        |at: &str, literal: &str| Error::new(format!("Expected {literal:?}")).at_token(input, at);
+    let mut rest = input
+        .strip_prefix(first)
+        .ok_or_else(|| expected(input, first))?;
+    let mut fields = Vec::with_capacity(count);
+    for (i, literal) in literals.iter().enumerate() {
+        let end = if literal.is_empty() {
+            assert_eq!(i + 1, count, "Fields in {pattern:?} must be separated");
+            rest.len()
+        } else {
+            rest.find(literal).ok_or_else(|| expected(rest, literal))?
+        };
+        fields.push(&rest[..end]);
+        rest = &rest[end + literal.len()..];
+    }
+    if !rest.is_empty() {
+        return Err(Error::new("Unexpected text").at_token(input, rest));
+    }
+    Ok(fields)
+}
+
+/// Reads a field of `input` found by `scan_fields`. Used by `scan!`.
+pub fn field<'a, T: Field<'a>>(input: &'a str, text: &'a str) -> Result<T> {
+    T::from_field(text).ok_or_else(|| {
+        let name = type_name::<T>().rsplit("::").next().unwrap_or("value");
+        Error::new(format!("Not a {name}")).at_token(input, text)
+    })
+}
+
+/// Matches a line against a pattern with `{}` for each field, and reads
+/// the fields into a tuple of the given types. Fields can be `&str` to
+/// borrow from the line.
+///
+/// ```
+/// let (node, left, right) = aoc::scan!("AAA = (BBB, CCC)", "{} = ({}, {})", &str, &str, &str).unwrap();
+/// assert_eq!((node, left, right), ("AAA", "BBB", "CCC"));
+///
+/// let (low, high, letter, password) =
+///     aoc::scan!("1-3 a: abcde", "{}-{} {}: {}", usize, usize, char, String).unwrap();
+/// assert_eq!((low, high, letter, password.as_str()), (1, 3, 'a', "abcde"));
+///
+/// let error = aoc::scan!("x-3 a: abcde", "{}-{} {}: {}", usize, usize, char, String).unwrap_err();
+/// assert_eq!(error.to_string(), r#"column 1: Not a usize: "x""#);
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($input:expr, $pattern:expr, $($t:ty),+ $(,)?) => {{
+        let input: &str = $input;
+        $crate::parse::scan_fields(input, $pattern, [$(stringify!($t)),+].len()).and_then(
+            |fields| {
+                let mut fields = fields.into_iter();
+                Ok(($($crate::parse::field::<$t>(input, fields.next().unwrap())?,)+))
+            },
+        )
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_blocks() {
+        let input = "\na\nb\r\n\r\n\n  \nc\n\nd";
+        assert_eq!(blocks(input).collect::<Vec<_>>(), ["a\nb", "c", "d"]);
+        assert_eq!(blocks("\n\n").count(), 0);
+        assert_eq!(header("seeds: 1 2"), ("seeds: 1 2", ""));
+    }
+
+    #[test]
+    fn finds_integers() {
+        let integers = |text| integer_tokens(text, true).collect::<Vec<_>>();
+        let text = "Sensor at x=-2, y=+15: beacon 10-20";
+        assert_eq!(integers(text), ["-2", "+15", "10", "20"]);
+        assert_eq!(integers("-3 a-4 (-5)"), ["-3", "4", "-5"]);
+        assert!(integers("no numbers").is_empty());
+        let unsigned = integer_tokens(text, false).collect::<Vec<_>>();
+        assert_eq!(unsigned, ["2", "15", "10", "20"]);
+    }
+
+    #[test]
+    fn scans_patterns() {
+        let line = "Game 12: 3 blue, 4 red";
+        assert_eq!(
+            scan!(line, "Game {}: {}", u32, &str).unwrap(),
+            (12, "3 blue, 4 red")
+        );
+        let error = scan!(line, "Game {}; {}", u32, &str).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"column 6: Expected "; ": "12: 3 blue, 4 red""#
+        );
+        let error = scan!(line, "Game {}: {} blue", u32, u32).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"column 16: Unexpected text: ", 4 red""#
+        );
+        let error = scan!(line, "Round {}: {}", u32, &str).unwrap_err();
+        assert_eq!(error.column, Some(1));
+    }
+}
@@ -0,0 +1,360 @@
+//! Arithmetic on ranges of numbers, for moving whole ranges of values at
+//! once instead of one value at a time.
+
+use std::cmp::{max, min};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Range, Sub};
+
+/// The half-open interval `start..end`, empty when `end <= start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub const fn new(start: T, end: T) -> Interval<T> {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The values in both intervals, which may be empty.
+    pub fn intersection(&self, other: &Interval<T>) -> Interval<T> {
+        Interval::new(max(self.start, other.start), min(self.end, other.end))
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// The values below `at`, and the rest.
+    pub fn split_at(&self, at: T) -> (Interval<T>, Interval<T>) {
+        let at = min(max(at, self.start), self.end);
+        (Interval::new(self.start, at), Interval::new(at, self.end))
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> Interval<T> {
+    pub fn len(&self) -> T {
+        max(self.start, self.end) - self.start
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Interval<T> {
+        Interval {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl<T> From<Interval<T>> for Range<T> {
+    fn from(interval: Interval<T>) -> Range<T> {
+        interval.start..interval.end
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A set of values, kept as sorted intervals that neither overlap nor touch.
+///
+/// ```
+/// # use aoc::ranges::RangeSet;
+/// let set = RangeSet::from_iter([0..5, 10..15]);
+/// let other = RangeSet::from(3..12);
+/// assert_eq!(set.union(&other), RangeSet::from(0..15));
+/// assert_eq!(set.intersection(&other), RangeSet::from_iter([3..5, 10..12]));
+/// assert_eq!(set.difference(&other), RangeSet::from_iter([0..3, 12..15]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    pub fn new() -> RangeSet<T> {
+        RangeSet { intervals: vec![] }
+    }
+
+    /// Adds the values in `interval`, merging it with the intervals it
+    /// overlaps or touches.
+    pub fn insert(&mut self, interval: impl Into<Interval<T>>) {
+        let mut interval = interval.into();
+        if interval.is_empty() {
+            return;
+        }
+        let from = self.intervals.partition_point(|i| i.end < interval.start);
+        let to = self.intervals.partition_point(|i| i.start <= interval.end);
+        if from < to {
+            interval.start = min(interval.start, self.intervals[from].start);
+            interval.end = max(interval.end, self.intervals[to - 1].end);
+        }
+        self.intervals.splice(from..to, [interval]);
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(i).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn max_end(&self) -> Option<T> {
+        self.intervals.last().map(|i| i.end)
+    }
+
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut union = self.clone();
+        for interval in other.iter() {
+            union.insert(interval);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut intersection = RangeSet::new();
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        while let (Some(x), Some(y)) = (a.peek().copied(), b.peek().copied()) {
+            intersection.insert(x.intersection(&y));
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        intersection
+    }
+
+    /// The values in this set and not in `other`.
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut difference = RangeSet::new();
+        for interval in self.iter() {
+            let mut start = interval.start;
+            let first = other.intervals.partition_point(|i| i.end <= interval.start);
+            for hole in other.intervals[first..]
+                .iter()
+                .take_while(|hole| hole.start < interval.end)
+            {
+                difference.insert(Interval::new(start, hole.start));
+                start = max(start, hole.end);
+            }
+            difference.insert(Interval::new(start, interval.end));
+        }
+        difference
+    }
+
+    /// The values below `at`, and the rest.
+    pub fn split_at(&self, at: T) -> (RangeSet<T>, RangeSet<T>) {
+        let (mut below, mut rest) = (RangeSet::new(), RangeSet::new());
+        for interval in self.iter() {
+            let (left, right) = interval.split_at(at);
+            below.insert(left);
+            rest.insert(right);
+        }
+        (below, rest)
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Sum> RangeSet<T> {
+    /// The number of values in the set.
+    pub fn len(&self) -> T {
+        self.iter().map(|i| i.len()).sum()
+    }
+}
+
+impl<T: Copy + Ord> Default for RangeSet<T> {
+    fn default() -> RangeSet<T> {
+        RangeSet::new()
+    }
+}
+
+impl<T: Copy + Ord> From<Interval<T>> for RangeSet<T> {
+    fn from(interval: Interval<T>) -> RangeSet<T> {
+        RangeSet::from_iter([interval])
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> RangeSet<T> {
+        RangeSet::from(Interval::from(range))
+    }
+}
+
+impl<T: Copy + Ord, I: Into<Interval<T>>> FromIterator<I> for RangeSet<T> {
+    fn from_iter<It: IntoIterator<Item = I>>(intervals: It) -> RangeSet<T> {
+        let mut set = RangeSet::new();
+        for interval in intervals {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+/// A piecewise-linear map that moves the values in each source interval to
+/// the same place after a destination, and leaves other values where they
+/// are. Where sources overlap, the one inserted first applies.
+///
+/// ```
+/// # use aoc::ranges::{RangeMap, RangeSet};
+/// let map = RangeMap::from_iter([(98..100, 50), (50..98, 52)]);
+/// assert_eq!((map.map(79), map.map(99), map.map(10)), (81, 51, 10));
+/// let seeds = RangeSet::from(95..100);
+/// assert_eq!(map.map_set(&seeds), RangeSet::from_iter([50..52, 97..100]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMap<T> {
+    rules: Vec<(Interval<T>, T)>,
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> RangeMap<T> {
+    pub fn new() -> RangeMap<T> {
+        RangeMap { rules: vec![] }
+    }
+
+    /// Moves the values in `source` to start at `destination`.
+    pub fn insert(&mut self, source: impl Into<Interval<T>>, destination: T) {
+        self.rules.push((source.into(), destination));
+    }
+
+    pub fn map(&self, value: T) -> T {
+        match self.rules.iter().find(|(source, _)| source.contains(value)) {
+            Some((source, destination)) => *destination + (value - source.start),
+            None => value,
+        }
+    }
+
+    /// Maps every value in `set`, an interval at a time.
+    pub fn map_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        let mut unmapped = set.clone();
+        let mut mapped = RangeSet::new();
+        for (source, destination) in &self.rules {
+            let source_set = RangeSet::from(*source);
+            for part in unmapped.intersection(&source_set).iter() {
+                mapped.insert(Interval::new(
+                    *destination + (part.start - source.start),
+                    *destination + (part.end - source.start),
+                ));
+            }
+            unmapped = unmapped.difference(&source_set);
+        }
+        mapped.union(&unmapped)
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Default for RangeMap<T> {
+    fn default() -> RangeMap<T> {
+        RangeMap::new()
+    }
+}
+
+impl<T, I> FromIterator<(I, T)> for RangeMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+    I: Into<Interval<T>>,
+{
+    fn from_iter<It: IntoIterator<Item = (I, T)>>(rules: It) -> RangeMap<T> {
+        let mut map = RangeMap::new();
+        for (source, destination) in rules {
+            map.insert(source, destination);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals() {
+        let interval = Interval::from(3..8);
+        assert!(interval.contains(3) && !interval.contains(8));
+        assert_eq!(interval.len(), 5);
+        assert_eq!(Interval::new(8, 3).len(), 0);
+        assert_eq!(interval.intersection(&(5..10).into()), Interval::new(5, 8));
+        assert!(!interval.overlaps(&(8..10).into()));
+        assert_eq!(
+            interval.split_at(5),
+            (Interval::new(3, 5), Interval::new(5, 8))
+        );
+        assert_eq!(interval.split_at(1).0, Interval::new(3, 3));
+        assert_eq!(interval.to_string(), "3..8");
+    }
+
+    #[test]
+    fn inserting_merges() {
+        let mut set = RangeSet::from_iter([10..12, 0..2, 5..6]);
+        assert_eq!(set.intervals().len(), 3);
+        set.insert(2..5);
+        assert_eq!(set, RangeSet::from_iter([0..6, 10..12]));
+        set.insert(7..7);
+        set.insert(1..11);
+        assert_eq!(set, RangeSet::from(0..12));
+        assert_eq!(
+            (set.len(), set.min(), set.max_end()),
+            (12, Some(0), Some(12))
+        );
+        assert!(set.contains(11) && !set.contains(12));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = RangeSet::from_iter([0..4, 6..10, 20..30]);
+        let b = RangeSet::from_iter([2..8, 9..25]);
+        assert_eq!(a.union(&b), RangeSet::from(0..30));
+        assert_eq!(
+            a.intersection(&b),
+            RangeSet::from_iter([2..4, 6..8, 9..10, 20..25])
+        );
+        assert_eq!(a.difference(&b), RangeSet::from_iter([0..2, 8..9, 25..30]));
+        assert_eq!(b.difference(&a), RangeSet::from_iter([4..6, 10..20]));
+        assert!(a.difference(&a).is_empty());
+        let (below, rest) = a.split_at(7);
+        assert_eq!(below, RangeSet::from_iter([0..4, 6..7]));
+        assert_eq!(rest, RangeSet::from_iter([7..10, 20..30]));
+    }
+
+    #[test]
+    fn maps_whole_ranges() {
+        let map = RangeMap::from_iter([(60u64..62, 50), (0..10, 100)]);
+        let seeds = RangeSet::from(55..68);
+        assert_eq!(
+            map.map_set(&seeds),
+            RangeSet::from_iter([50..52, 55..60, 62..68])
+        );
+        let each = (55..68).map(|seed| map.map(seed)).collect::<Vec<_>>();
+        assert_eq!(each, [55, 56, 57, 58, 59, 50, 51, 62, 63, 64, 65, 66, 67]);
+        let first_wins = RangeMap::from_iter([(0..10, 100), (5..15, 200)]);
+        assert_eq!(
+            first_wins.map_set(&RangeSet::from(0..15)),
+            RangeSet::from_iter([100..110, 205..210])
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Machine-readable records of runs, printed with `--format json`.
+
+use crate::allocations::AllocStats;
+use crate::{history, Aoc, Day, Run, Verdict};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How the outcome of a run is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Log lines like `Part 1: 142 ✔ (1.234µs)`.
+    #[default]
+    Text,
+    /// One JSON record per run on stdout, with the log on stderr.
+    Json,
+}
+
+impl Format {
+    /// Reads `--format text|json` from the arguments, `text` when not given.
+    pub fn from_args(args: &[String]) -> Result<Format, String> {
+        match args.iter().position(|arg| arg == "--format") {
+            Some(i) => args
+                .get(i + 1)
+                .ok_or("--format needs text or json")?
+                .parse(),
+            None => Ok(Format::Text),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Format, String> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format {format}, use text or json")),
+        }
+    }
+}
+
+/// Everything about one run of a day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub day: u8,
+    /// Name of the input, like `input` or `example`.
+    pub input: String,
+    /// Where the input came from, like `puzzle input` or `file big.txt`.
+    pub source: String,
+    pub parse_ns: u64,
+    /// What parsing allocated, when allocations are counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_allocs: Option<AllocStats>,
+    /// Why parsing failed, in which case there are no parts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub parts: Vec<PartRecord>,
+    pub total_ns: u64,
+    /// Peak resident memory of the process, where the platform tells it.
+    pub peak_memory_bytes: Option<u64>,
+    /// Output of `git describe --always --dirty`.
+    pub commit: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+/// The answer of one part and how it compares to the known one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartRecord {
+    pub part: u8,
+    /// `None` if the part is not solved yet.
+    pub answer: Option<String>,
+    /// `None` if the right answer is not known.
+    pub correct: Option<bool>,
+    /// The right answer, when the answer is wrong.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    pub solve_ns: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocs: Option<AllocStats>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RunRecord {
+    pub fn new(day: &Day, aoc: &Aoc, run: &Run) -> RunRecord {
+        let answers = day.known_answers(aoc).unwrap_or_default();
+        RunRecord {
+            day: day.number,
+            input: aoc.input_name().to_string(),
+            source: aoc.source().to_string(),
+            parse_ns: nanos(run.parse),
+            parse_allocs: run.parse_allocs,
+            error: run.error.as_ref().map(ToString::to_string),
+            parts: run
+                .parts
+                .iter()
+                .map(|part| {
+                    let answer = part.answer.as_deref();
+                    let (correct, expected) = match part.error {
+                        Some(_) => (None, None),
+                        None => match answers.check(aoc.input_name(), part.part, answer) {
+                            Verdict::Unknown => (None, None),
+                            Verdict::Correct => (Some(true), None),
+                            Verdict::Wrong { expected } => (Some(false), Some(expected.into())),
+                        },
+                    };
+                    PartRecord {
+                        part: part.part,
+                        answer: part.answer.clone(),
+                        correct,
+                        expected,
+                        solve_ns: nanos(part.time),
+                        allocs: part.allocs,
+                        error: part.error.as_ref().map(ToString::to_string),
+                    }
+                })
+                .collect(),
+            total_ns: nanos(run.total()),
+            peak_memory_bytes: peak_memory(),
+            commit: history::commit(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Run records are always serializable")
+    }
+}
+
+/// Runs the day once, logs it like `bench` and prints its record as one line
+/// of JSON.
+pub fn run_json(day: &Day, aoc: &Aoc) -> Run {
+    let run = crate::bench(day, aoc);
+    println!("{}", RunRecord::new(day, aoc, &run).to_json());
+    run
+}
+
+/// The most memory the process has had resident, from `VmHWM` in
+/// `/proc/self/status`. `None` on platforms without it.
+pub fn peak_memory() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kb = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kb * 1024)
+}
+
+/// Resets the peak that `peak_memory` reports to the memory resident now,
+/// by writing `5` to `/proc/self/clear_refs`. False on platforms without it.
+pub fn reset_peak_memory() -> bool {
+    fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, PartRun, Source};
+
+    fn day() -> Day {
+        Day {
+            number: 1,
+            input: "",
+            examples: &[],
+            answers: "[input]\npart1 = 142\npart2 = 281\n",
+            solve: |_| unreachable!(),
+        }
+    }
+
+    #[test]
+    fn format_from_args() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(Format::from_args(&args(&["1"])), Ok(Format::Text));
+        assert_eq!(
+            Format::from_args(&args(&["1", "--format", "json"])),
+            Ok(Format::Json)
+        );
+        assert!(Format::from_args(&args(&["--format", "xml"])).is_err());
+        assert!(Format::from_args(&args(&["--format"])).is_err());
+    }
+
+    #[test]
+    fn record_of_run() {
+        let aoc = Aoc::new(Source::Input, "", None);
+        let run = Run {
+            parse: Duration::from_micros(3),
+            parse_allocs: None,
+            error: None,
+            parts: vec![
+                PartRun {
+                    part: 1,
+                    answer: Some("142".to_string()),
+                    error: None,
+                    time: Duration::from_micros(5),
+                    allocs: Some(AllocStats {
+                        allocations: 2,
+                        bytes: 64,
+                        peak_bytes: 48,
+                    }),
+                },
+                PartRun {
+                    part: 2,
+                    answer: Some("280".to_string()),
+                    error: None,
+                    time: Duration::from_micros(7),
+                    allocs: None,
+                },
+            ],
+        };
+        let record = RunRecord::new(&day(), &aoc, &run);
+        assert_eq!((record.day, record.input.as_str()), (1, "input"));
+        assert_eq!(record.source, "puzzle input");
+        assert_eq!((record.parse_ns, record.total_ns), (3000, 15000));
+        assert_eq!(record.parts[0].correct, Some(true));
+        assert_eq!(record.parts[1].correct, Some(false));
+        assert_eq!(record.parts[1].expected.as_deref(), Some("281"));
+
+        let json = record.to_json();
+        assert!(json.contains(r#""part":1,"answer":"142","correct":true,"solve_ns":5000,"allocs":{"allocations":2,"bytes":64,"peak_bytes":48}"#));
+        assert!(!json.contains(r#""parse_allocs""#));
+        assert!(!json.contains(r#""error""#));
+        assert_eq!(serde_json::from_str::<RunRecord>(&json).unwrap(), record);
+    }
+
+    #[test]
+    fn record_of_failed_run() {
+        let aoc = Aoc::new(Source::Example(None), "", None);
+        let run = Run {
+            parse: Duration::ZERO,
+            parse_allocs: None,
+            error: Some(Error::new("Bad input").at_line(2)),
+            parts: vec![],
+        };
+        let record = RunRecord::new(&day(), &aoc, &run);
+        assert_eq!(record.error.as_deref(), Some("line 2: Bad input"));
+        assert!(record.parts.is_empty());
+    }
+}
@@ -0,0 +1,420 @@
+//! Searches over graphs given by a function from a node to its neighbors.
+//! None of them recurse, so they work on graphs of any depth.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a search found: the cost to reach each node and the node it was
+/// reached from.
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    pub costs: HashMap<N, C>,
+    pub predecessors: HashMap<N, N>,
+    /// The nodes in the order they were reached.
+    pub order: Vec<N>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Paths<N, C> {
+    fn new() -> Paths<N, C> {
+        Paths {
+            costs: HashMap::new(),
+            predecessors: HashMap::new(),
+            order: vec![],
+        }
+    }
+
+    fn reach(&mut self, node: N, cost: C, from: Option<N>) {
+        if let Some(from) = from {
+            self.predecessors.insert(node.clone(), from);
+        }
+        self.costs.insert(node.clone(), cost);
+        self.order.push(node);
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.costs.contains_key(node)
+    }
+
+    /// The nodes from the start to `node`, if it was reached.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.contains(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search from `start`, with the number of steps to each node
+/// as its cost.
+pub fn bfs<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Paths<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new();
+    paths.reach(start.clone(), 0, None);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, steps)) = queue.pop_front() {
+        for next in neighbors(&node) {
+            if !paths.contains(&next) {
+                paths.reach(next.clone(), steps + 1, Some(node.clone()));
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    paths
+}
+
+/// Depth-first search from `start`, with the depth in the search tree as
+/// the cost of each node. Nodes are reached in preorder, neighbors in the
+/// order they are given.
+pub fn dfs<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Paths<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new();
+    let mut stack = vec![(start, 0, None)];
+    while let Some((node, depth, from)) = stack.pop() {
+        if paths.contains(&node) {
+            continue;
+        }
+        paths.reach(node.clone(), depth, from);
+        let next = neighbors(&node).into_iter().collect::<Vec<_>>();
+        for next in next.into_iter().rev() {
+            if !paths.contains(&next) {
+                stack.push((next, depth + 1, Some(node.clone())));
+            }
+        }
+    }
+    paths
+}
+
+/// The nodes reachable from `start`, itself included.
+pub fn flood_fill<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for next in neighbors(&node) {
+            if seen.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+    seen
+}
+
+/// A node waiting in a priority queue, ordered so that the cheapest comes
+/// out of a `BinaryHeap` first.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+/// Cheapest paths from `start` to every reachable node, with `neighbors`
+/// giving each neighbor with the cost of moving to it. Costs must not be
+/// negative, and `C::default()` is the cost of not moving.
+pub fn dijkstra<N, C, I>(start: N, neighbors: impl FnMut(&N) -> I) -> Paths<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    cheapest_first(start, neighbors, |_| C::default(), |_| false).0
+}
+
+/// The cheapest path from `start` to a node that `is_goal`, and its cost.
+/// The `heuristic` must be consistent: for every step from `a` to `b`,
+/// `heuristic(a) <= cost + heuristic(b)`, and it must be zero at goals.
+/// Nodes are settled once, so with a heuristic that is only admissible, never
+/// more than the real cost to a goal, the path found may not be the cheapest.
+/// Manhattan distance on a grid where each step costs at least 1 is
+/// consistent.
+pub fn astar<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (paths, goal) = cheapest_first(start, neighbors, heuristic, is_goal);
+    let goal = goal?;
+    Some((paths.path_to(&goal)?, paths.cost(&goal)?))
+}
+
+/// Settles nodes cheapest first until a goal is settled, which it returns.
+fn cheapest_first<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (Paths<N, C>, Option<N>)
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new();
+    // The cheapest way found so far to each node not yet settled.
+    let mut best = HashMap::from([(start.clone(), (C::default(), None))]);
+    let mut queue = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        if paths.contains(&node) || best.get(&node).is_some_and(|(best, _)| cost > *best) {
+            continue;
+        }
+        let from = best.remove(&node).and_then(|(_, from)| from);
+        paths.reach(node.clone(), cost, from);
+        if is_goal(&node) {
+            return (paths, Some(node));
+        }
+        for (next, step) in neighbors(&node) {
+            let cost = cost + step;
+            if paths.contains(&next) || best.get(&next).is_some_and(|(best, _)| cost >= *best) {
+                continue;
+            }
+            best.insert(next.clone(), (cost, Some(node.clone())));
+            queue.push(Queued {
+                priority: cost + heuristic(&next),
+                cost,
+                node: next,
+            });
+        }
+    }
+    (paths, None)
+}
+
+/// Walks around a loop from `start`: to one of its neighbors, and from then
+/// on to a neighbor other than the one it came from, until it is back at
+/// `start`. Each neighbor of `start` is tried in turn, so dead-end spurs on
+/// `start` are passed over. Returns the nodes of the first loop found in
+/// order from `start`, or `None` if every walk comes to a dead end or into a
+/// loop without `start`.
+pub fn walk_cycle<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let firsts: Vec<N> = neighbors(&start).into_iter().collect();
+    firsts
+        .into_iter()
+        .filter(|first| *first != start)
+        .find_map(|first| walk_cycle_from(&start, first, &mut neighbors))
+}
+
+/// Walks from `start` through `first` until back at `start`.
+fn walk_cycle_from<N, I>(start: &N, first: N, neighbors: &mut impl FnMut(&N) -> I) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut cycle = vec![start.clone()];
+    let mut seen = HashSet::from([start.clone()]);
+    let mut previous = start.clone();
+    let mut next = first;
+    loop {
+        if next == *start {
+            return Some(cycle);
+        }
+        if !seen.insert(next.clone()) {
+            return None;
+        }
+        cycle.push(next.clone());
+        let current = next;
+        next = neighbors(&current)
+            .into_iter()
+            .find(|next| *next != previous)?;
+        previous = current;
+    }
+}
+
+/// Where the sequence `start`, `next(start)`, `next(next(start))`, …
+/// starts repeating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Index of the first element of the sequence that repeats.
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The index of an element before the end of the first cycle that is
+    /// equal to the element at `index`.
+    pub fn equivalent(&self, index: usize) -> usize {
+        if index < self.start {
+            index
+        } else {
+            self.start + (index - self.start) % self.length
+        }
+    }
+}
+
+/// Follows the sequence from `start` until an element repeats.
+pub fn find_cycle<N>(start: N, mut next: impl FnMut(&N) -> N) -> Cycle
+where
+    N: Eq + Hash + Clone,
+{
+    let mut seen = HashMap::new();
+    let mut current = start;
+    for index in 0.. {
+        if let Some(start) = seen.insert(current.clone(), index) {
+            return Cycle {
+                start,
+                length: index - start,
+            };
+        }
+        current = next(&current);
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, Pos};
+
+    const MAZE: &str = "\
+..#....
+.##.##.
+....#..
+.####.#
+.......
+";
+
+    fn open(grid: &Grid<bool>) -> impl FnMut(&Pos) -> Vec<Pos> + '_ {
+        |pos| grid.neighbors4(*pos).filter(|next| grid[*next]).collect()
+    }
+
+    fn maze() -> Grid<bool> {
+        Grid::parse(MAZE, |c| c == '.').unwrap()
+    }
+
+    #[test]
+    fn bfs_finds_shortest_paths() {
+        let maze = maze();
+        let paths = bfs((0, 0), open(&maze));
+        assert_eq!(paths.cost(&(0, 3)), Some(7));
+        assert_eq!(paths.cost(&(0, 2)), None);
+        let path = paths.path_to(&(0, 3)).unwrap();
+        assert_eq!(path.len(), 8);
+        assert_eq!((path[0], path[7]), ((0, 0), (0, 3)));
+        assert!(path
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+        assert_eq!(paths.order[0], (0, 0));
+    }
+
+    #[test]
+    fn dfs_and_flood_fill_reach_the_same() {
+        let maze = maze();
+        let reached = flood_fill((0, 0), open(&maze));
+        let paths = dfs((0, 0), open(&maze));
+        assert_eq!(paths.order.len(), reached.len());
+        assert!(paths.order.iter().all(|pos| reached.contains(pos)));
+        assert_eq!(reached.len(), MAZE.matches('.').count());
+        assert_eq!(paths.order[..3], [(0, 0), (1, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_find_cheapest_paths() {
+        // Going down costs 10, every other step 1.
+        let maze = maze();
+        let weighted = |pos: &Pos| {
+            let pos = *pos;
+            open(&maze)(&pos)
+                .into_iter()
+                .map(move |next| (next, if next.0 > pos.0 { 10 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let paths = dijkstra((0, 0), weighted);
+        assert_eq!(paths.cost(&(2, 0)), Some(20));
+        assert_eq!(paths.cost(&(0, 3)), Some(25));
+
+        let goal = (0, 6);
+        let (path, cost) = astar(
+            (0, 0),
+            weighted,
+            |pos: &Pos| pos.0.abs_diff(goal.0) + pos.1.abs_diff(goal.1),
+            |pos| *pos == goal,
+        )
+        .unwrap();
+        assert_eq!(Some(cost), dijkstra((0, 0), weighted).cost(&goal));
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        assert_eq!(astar((0, 0), weighted, |_| 0, |pos| *pos == (0, 2)), None);
+    }
+
+    #[test]
+    fn walks_cycles() {
+        let ring = |n: &u32| [(n + 1) % 6, (n + 5) % 6];
+        assert_eq!(walk_cycle(0, ring), Some(vec![0, 1, 2, 3, 4, 5]));
+        let path = |n: &u32| [n + 1, n.saturating_sub(1)].into_iter().filter(|n| *n < 4);
+        assert_eq!(walk_cycle(0, path), None);
+        // 0 has a spur to 9 before the ring 0-1-2-3.
+        let spur = |n: &u32| match n {
+            0 => vec![9, 1, 3],
+            9 => vec![0],
+            n => vec![(n + 1) % 4, (n + 3) % 4],
+        };
+        assert_eq!(walk_cycle(0, spur), Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn finds_repeating_sequence() {
+        // 3, 6, 12, 24, 48, 96, 92, 84, 68, 36, 72, 44, 88, 76, 52, 4, 8, 16, 32, 64, 28, 56, 12
+        let cycle = find_cycle(3, |n| n * 2 % 100);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 2,
+                length: 20
+            }
+        );
+        assert_eq!(cycle.equivalent(1), 1);
+        assert_eq!(cycle.equivalent(22), 2);
+        assert_eq!(cycle.equivalent(1_000_000_000), 20);
+    }
+}
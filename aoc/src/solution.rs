@@ -0,0 +1,259 @@
+use crate::allocations::{self, AllocStats};
+use crate::input::Lines;
+use crate::{Aoc, Error};
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// A puzzle solution split into its parsing and solving stages.
+///
+/// Parts return `None` while they are not solved yet.
+pub trait Solution {
+    type Parsed<'a>;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Self::Parsed<'_>;
+    fn part1(parsed: &Self::Parsed<'_>) -> Option<Self::Part1>;
+    fn part2(parsed: &Self::Parsed<'_>) -> Option<Self::Part2>;
+}
+
+/// A solution whose stages can fail, for inputs that are not assumed to be
+/// well formed. Every `Solution` is one that never fails.
+pub trait TrySolution {
+    type Parsed<'a>;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn try_parse(input: &str) -> Result<Self::Parsed<'_>, Error>;
+    fn try_part1(parsed: &Self::Parsed<'_>) -> Result<Option<Self::Part1>, Error>;
+    fn try_part2(parsed: &Self::Parsed<'_>) -> Result<Option<Self::Part2>, Error>;
+
+    /// Parses the input a line at a time instead of as one `&str`, so that a
+    /// pipe on stdin is streamed rather than read into memory whole. The
+    /// default, `None`, leaves the lines unread and parses with `try_parse`.
+    fn try_parse_lines<'a>(_lines: &mut Lines<'a>) -> Option<Result<Self::Parsed<'a>, Error>> {
+        None
+    }
+}
+
+impl<S: Solution> TrySolution for S {
+    type Parsed<'a> = S::Parsed<'a>;
+    type Part1 = S::Part1;
+    type Part2 = S::Part2;
+
+    fn try_parse(input: &str) -> Result<Self::Parsed<'_>, Error> {
+        Ok(S::parse(input))
+    }
+
+    fn try_part1(parsed: &Self::Parsed<'_>) -> Result<Option<Self::Part1>, Error> {
+        Ok(S::part1(parsed))
+    }
+
+    fn try_part2(parsed: &Self::Parsed<'_>) -> Result<Option<Self::Part2>, Error> {
+        Ok(S::part2(parsed))
+    }
+}
+
+/// The answer and duration of one part.
+#[derive(Debug, Clone)]
+pub struct PartRun {
+    pub part: u8,
+    /// `None` if the part is not solved yet.
+    pub answer: Option<String>,
+    /// Why the part failed, if it did.
+    pub error: Option<Error>,
+    pub time: Duration,
+    /// What the part allocated, when allocations are counted.
+    pub allocs: Option<AllocStats>,
+}
+
+/// The outcome of running a solution once.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub parse: Duration,
+    /// What parsing allocated, when allocations are counted.
+    pub parse_allocs: Option<AllocStats>,
+    /// Why parsing failed, in which case no parts were run.
+    pub error: Option<Error>,
+    /// The parts that were run, in order.
+    pub parts: Vec<PartRun>,
+}
+
+impl Run {
+    pub fn part(&self, part: u8) -> Option<&PartRun> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+
+    /// The errors of the stages that failed.
+    pub fn errors(&self) -> impl Iterator<Item = &Error> {
+        self.error
+            .iter()
+            .chain(self.parts.iter().filter_map(|p| p.error.as_ref()))
+    }
+
+    pub fn failed(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts.iter().map(|p| p.time).sum::<Duration>()
+    }
+}
+
+/// Parses the input and solves the parts selected in `aoc`, timing each stage.
+/// Solutions that parse lines get them streamed, and reading them is part of
+/// parsing. Otherwise a pipe on stdin is read before parsing starts, so that
+/// reading it is not timed.
+pub fn solve<S: TrySolution>(aoc: &Aoc) -> Run {
+    let mut lines = aoc.stream_lines();
+    let now = Instant::now();
+    let (streamed, allocs) = allocations::measure(|| S::try_parse_lines(&mut lines));
+    let (parsed, parse, parse_allocs) = match streamed {
+        Some(parsed) => (parsed, now.elapsed(), allocs),
+        None => {
+            let input = aoc.try_read_input_string();
+            let now = Instant::now();
+            let (parsed, allocs) = allocations::measure(|| input.and_then(S::try_parse));
+            (parsed, now.elapsed(), allocs)
+        }
+    };
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            return Run {
+                parse,
+                parse_allocs,
+                error: Some(error.or_file(aoc.input_file())),
+                parts: vec![],
+            }
+        }
+    };
+
+    let mut parts = vec![];
+    if aoc.runs_part(1) {
+        parts.push(solve_part(aoc, 1, || S::try_part1(&parsed)));
+    }
+    if aoc.runs_part(2) {
+        parts.push(solve_part(aoc, 2, || S::try_part2(&parsed)));
+    }
+    Run {
+        parse,
+        parse_allocs,
+        error: None,
+        parts,
+    }
+}
+
+fn solve_part<T: Display>(
+    aoc: &Aoc,
+    part: u8,
+    f: impl FnOnce() -> Result<Option<T>, Error>,
+) -> PartRun {
+    let now = Instant::now();
+    let (answer, allocs) = allocations::measure(f);
+    let time = now.elapsed();
+    let (answer, error) = match answer {
+        Ok(answer) => (answer.map(|a| a.to_string()), None),
+        Err(error) => (None, Some(error.or_file(aoc.input_file()))),
+    };
+    PartRun {
+        part,
+        answer,
+        error,
+        time,
+        allocs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{try_parse_numbers, Input, Source};
+    use std::io;
+
+    struct Sum;
+
+    impl TrySolution for Sum {
+        type Parsed<'a> = Vec<u32>;
+        type Part1 = u32;
+        type Part2 = u32;
+
+        fn try_parse(input: &str) -> Result<Vec<u32>, Error> {
+            try_parse_numbers(input)
+        }
+
+        fn try_part1(numbers: &Vec<u32>) -> Result<Option<u32>, Error> {
+            Ok(Some(numbers.iter().sum()))
+        }
+
+        fn try_part2(_: &Vec<u32>) -> Result<Option<u32>, Error> {
+            Err(Error::new("Part 2 has no answer"))
+        }
+    }
+
+    /// Sums the numbers of each line, parsing lines as they are streamed.
+    struct LineSums;
+
+    impl TrySolution for LineSums {
+        type Parsed<'a> = Vec<u32>;
+        type Part1 = u32;
+        type Part2 = u32;
+
+        fn try_parse(_: &str) -> Result<Vec<u32>, Error> {
+            unreachable!("the lines are parsed")
+        }
+
+        fn try_part1(sums: &Vec<u32>) -> Result<Option<u32>, Error> {
+            Ok(sums.iter().max().copied())
+        }
+
+        fn try_part2(sums: &Vec<u32>) -> Result<Option<u32>, Error> {
+            Ok(Some(sums.iter().sum()))
+        }
+
+        fn try_parse_lines<'a>(lines: &mut Lines<'a>) -> Option<Result<Vec<u32>, Error>> {
+            Some(
+                lines
+                    .map(|line| try_parse_numbers::<u32, Vec<_>>(&line?).map(|n| n.iter().sum()))
+                    .collect(),
+            )
+        }
+    }
+
+    #[test]
+    fn streams_lines_to_solutions_that_parse_them() {
+        let input = Input::from_reader(io::Cursor::new("1 2\n3 4\n"));
+        let aoc = Aoc::new(Source::Stdin, input, None);
+        let run = solve::<LineSums>(&aoc);
+        assert_eq!(run.part(1).unwrap().answer.as_deref(), Some("7"));
+        assert_eq!(run.part(2).unwrap().answer.as_deref(), Some("10"));
+        assert!(aoc.try_read_input_string().is_err(), "stdin was streamed");
+
+        let input = Input::from_reader(io::Cursor::new("1 2\n"));
+        let aoc = Aoc::new(Source::Stdin, input, None);
+        assert_eq!(
+            solve::<Sum>(&aoc).part(1).unwrap().answer.as_deref(),
+            Some("3")
+        );
+        let lines = aoc.stream_lines().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            lines,
+            ["1 2"],
+            "stdin was read whole and can still be streamed"
+        );
+    }
+
+    #[test]
+    fn reports_failed_stages() {
+        let run = solve::<Sum>(&Aoc::new(Source::Input, "1 2 3", None));
+        assert_eq!(run.part(1).unwrap().answer.as_deref(), Some("6"));
+        assert_eq!(
+            run.errors().map(Error::to_string).collect::<Vec<_>>(),
+            ["input: Part 2 has no answer"]
+        );
+
+        let run = solve::<Sum>(&Aoc::new(Source::Input, "1 -2 3", None));
+        assert!(run.parts.is_empty());
+        assert_eq!(run.error.unwrap().column, Some(3));
+    }
+}
@@ -0,0 +1,96 @@
+use aoc::{Day, Solution};
+use log::debug;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub const DAY: Day = Day {
+    number: 1,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day01>,
+};
+
+struct Day01;
+
+impl Solution for Day01 {
+    type Parsed<'a> = Vec<&'a str>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Vec<&str> {
+        input.lines().collect()
+    }
+
+    fn part1(lines: &Vec<&str>) -> Option<u32> {
+        Some(part1(lines))
+    }
+
+    fn part2(lines: &Vec<&str>) -> Option<u32> {
+        Some(part2(lines))
+    }
+}
+
+fn part1(lines: &[&str]) -> u32 {
+    lines.iter().map(|s| parse_line(s)).sum()
+}
+
+fn parse_line(line: &str) -> u32 {
+    let first = first_digit(line.chars());
+    let last = first_digit(line.chars().rev());
+    first * 10 + last
+}
+
+fn first_digit(mut chars: impl Iterator<Item = char>) -> u32 {
+    chars
+        .find(|c| c.is_ascii_digit())
+        .and_then(|c| c.to_digit(10))
+        .unwrap()
+}
+
+const WORDS: [(u32, &str); 18] = [
+    (1, "one"),
+    (2, "two"),
+    (3, "three"),
+    (4, "four"),
+    (5, "five"),
+    (6, "six"),
+    (7, "seven"),
+    (8, "eight"),
+    (9, "nine"),
+    (1, "1"),
+    (2, "2"),
+    (3, "3"),
+    (4, "4"),
+    (5, "5"),
+    (6, "6"),
+    (7, "7"),
+    (8, "8"),
+    (9, "9"),
+];
+
+fn part2(lines: &[&str]) -> u32 {
+    lines.iter().map(|s| parse_line_with_words(s)).sum()
+}
+
+fn parse_line_with_words(line: &str) -> u32 {
+    let indexes = WORDS
+        .iter()
+        .flat_map(|(i, word)| line.match_indices(word).map(|w| (w.0, *i)))
+        .collect::<Vec<_>>();
+
+    let first = indexes.iter().min().expect("No digit on line").1;
+    let last = indexes.iter().max().expect("No digit on line").1;
+
+    debug!(
+        "{line} first: {} last: {} indexes: {:?}",
+        first, last, indexes
+    );
+
+    first * 10 + last
+}
+
+#[cfg(test)]
+mod tests {
+    aoc::answer_tests!(crate::DAY);
+}
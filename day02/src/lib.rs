@@ -0,0 +1,82 @@
+use aoc::context::ParseContext;
+use aoc::{scan, Day, Error, TrySolution};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub const DAY: Day = Day {
+    number: 2,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day02>,
+};
+
+struct Day02;
+
+impl TrySolution for Day02 {
+    type Parsed<'a> = Vec<[u32; 3]>;
+    type Part1 = usize;
+    type Part2 = u32;
+
+    fn try_parse(input: &str) -> aoc::Result<Vec<[u32; 3]>> {
+        ParseContext::new(input)
+            .lines()
+            .map(|line| parse_line(line.text).map_err(|e| line.locate(e, line.text)))
+            .collect()
+    }
+
+    fn try_part1(maxes: &Vec<[u32; 3]>) -> aoc::Result<Option<usize>> {
+        Ok(Some(part1(maxes)))
+    }
+
+    fn try_part2(maxes: &Vec<[u32; 3]>) -> aoc::Result<Option<u32>> {
+        Ok(Some(part2(maxes)))
+    }
+}
+
+fn part1(maxes: &[[u32; 3]]) -> usize {
+    maxes
+        .iter()
+        .enumerate()
+        .filter(|(_, [r, g, b])| *r <= 12 && *g <= 13 && *b <= 14)
+        .map(|(i, _)| i + 1)
+        .sum()
+}
+
+fn part2(maxes: &[[u32; 3]]) -> u32 {
+    maxes.iter().map(|[r, g, b]| r * g * b).sum()
+}
+
+/// The most cubes of each color shown at once, as red, green and blue.
+fn parse_line(line: &str) -> aoc::Result<[u32; 3]> {
+    let (_, draws) = scan!(line, "Game {}: {}", u32, &str)?;
+    let mut maxes = [0; 3];
+    for cubes in draws.split([',', ';']).map(str::trim) {
+        let (count, color) = scan!(cubes, "{} {}", u32, &str)
+            .map_err(|_| Error::new("Expected a count and a color").at_token(line, cubes))?;
+        let i = match color {
+            "red" => 0,
+            "green" => 1,
+            "blue" => 2,
+            _ => return Err(Error::new("Unknown color").at_token(line, color)),
+        };
+        maxes[i] = maxes[i].max(count);
+    }
+    Ok(maxes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc::answer_tests!(crate::DAY);
+
+    #[test]
+    fn points_at_unknown_color() {
+        let line = "Game 1: 3 blue, 4 rad; 1 red";
+        let error = parse_line(line).unwrap_err();
+        assert_eq!(error.to_string(), r#"column 19: Unknown color: "rad""#);
+        let error = parse_line("Game 1: 3 blue, red").unwrap_err();
+        assert_eq!(error.column, Some(17));
+    }
+}
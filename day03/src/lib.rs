@@ -0,0 +1,142 @@
+use std::cmp::min;
+
+use aoc::grid::Grid;
+use aoc::{Day, TrySolution};
+use log::debug;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub const DAY: Day = Day {
+    number: 3,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day03>,
+};
+
+struct Day03;
+
+impl TrySolution for Day03 {
+    type Parsed<'a> = Grid<char>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn try_parse(input: &str) -> aoc::Result<Grid<char>> {
+        Grid::parse(input, |c| c)
+    }
+
+    fn try_part1(map: &Grid<char>) -> aoc::Result<Option<i32>> {
+        Ok(Some(part1(map)))
+    }
+
+    fn try_part2(map: &Grid<char>) -> aoc::Result<Option<i32>> {
+        Ok(Some(part2(map)))
+    }
+}
+
+fn part1(map: &Grid<char>) -> i32 {
+    let mut sum: i32 = 0;
+    for y in 0..map.height() {
+        let mut x = 0;
+        while x < map.width() {
+            if map[(y, x)].is_ascii_digit() {
+                let (number, x2) = read_number(map, y, x);
+                let added = check_for_symbols(map, y, x, x2);
+                debug!("y: {y}, x:{x}, {x2}: {number} {added}");
+                if added {
+                    sum += number
+                }
+                x = x2;
+            }
+            x += 1;
+        }
+    }
+
+    sum
+}
+
+fn check_for_symbols(map: &Grid<char>, y: usize, x: usize, x2: usize) -> bool {
+    let start_y = y.saturating_sub(1);
+    let end_y = min(y + 1, map.height() - 1);
+    let start_x = x.saturating_sub(1);
+    let end_x = min(x2 + 1, map.width() - 1);
+
+    map.view((start_y, start_x), end_y - start_y + 1, end_x - start_x + 1)
+        .rows()
+        .any(|row| row.iter().any(|c| !c.is_ascii_digit() && *c != '.'))
+}
+
+fn read_number(map: &Grid<char>, y: usize, x: usize) -> (i32, usize) {
+    let mut x = x;
+    let mut chars: Vec<char> = vec![];
+    while x < map.width() && map[(y, x)].is_ascii_digit() {
+        chars.push(map[(y, x)]);
+        x += 1;
+    }
+    (
+        chars.iter().collect::<String>().parse::<i32>().unwrap(),
+        x - 1,
+    )
+}
+
+fn part2(map: &Grid<char>) -> i32 {
+    let mut sum: i32 = 0;
+    for y in 0..map.height() {
+        let mut x = 0;
+        while x < map.width() {
+            if map[(y, x)] == '*' {
+                debug!("Potential cog at {y}, {x}");
+                let gear_ratio = gear_ratio(map, y, x);
+                debug!("gear_ratio {gear_ratio:?}");
+                sum += gear_ratio;
+            }
+            x += 1;
+        }
+    }
+    sum
+}
+
+fn gear_ratio(map: &Grid<char>, y: usize, x: usize) -> i32 {
+    let start_y = y.saturating_sub(1);
+    let end_y = min(y + 1, map.height() - 1);
+    let start_x = x.saturating_sub(1);
+    let end_x = min(x + 1, map.width() - 1);
+
+    let mut numbers: Vec<i32> = vec![];
+
+    for y in start_y..=end_y {
+        let mut x: usize = start_x;
+        while x <= end_x {
+            if map[(y, x)].is_ascii_digit() {
+                let mut number_start = x as i32;
+                while number_start > 0 && map[(y, (number_start - 1) as usize)].is_ascii_digit() {
+                    number_start -= 1;
+                }
+                let (number, x2) = read_number(map, y, number_start as usize);
+                debug!("number: {number}, x2:{x2}");
+                numbers.push(number);
+                x = x2;
+            }
+            x += 1;
+        }
+    }
+
+    if numbers.len() == 2 {
+        numbers[0] * numbers[1]
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc::answer_tests!(crate::DAY);
+
+    #[test]
+    fn ragged_map_is_an_error() {
+        let error = Day03::try_parse("ab\nc\n").unwrap_err();
+        assert_eq!(error.line, Some(2));
+    }
+}
@@ -0,0 +1,133 @@
+use aoc::context::{Line, ParseContext};
+use aoc::{try_extract_numbers, Day, TrySolution};
+use log::debug;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+#[derive(Debug)]
+struct Cards {
+    winning_numbers: usize,
+    number_of_cards: usize,
+}
+
+impl Cards {
+    pub fn new(winning_numbers: usize) -> Cards {
+        Cards {
+            winning_numbers,
+            number_of_cards: 1,
+        }
+    }
+
+    pub fn add_cards(&mut self, amount: usize) {
+        self.number_of_cards += amount;
+    }
+}
+
+impl Display for Cards {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} cards with {} winning numbers",
+            self.number_of_cards, self.winning_numbers
+        )
+    }
+}
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub const DAY: Day = Day {
+    number: 4,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day04>,
+};
+
+struct Day04;
+
+impl TrySolution for Day04 {
+    type Parsed<'a> = Vec<usize>;
+    type Part1 = u32;
+    type Part2 = usize;
+
+    fn try_parse(input: &str) -> aoc::Result<Vec<usize>> {
+        counts(input)
+    }
+
+    fn try_part1(counts: &Vec<usize>) -> aoc::Result<Option<u32>> {
+        Ok(Some(part1(counts)))
+    }
+
+    fn try_part2(counts: &Vec<usize>) -> aoc::Result<Option<usize>> {
+        Ok(Some(part2(counts)))
+    }
+}
+
+fn counts(input: &str) -> aoc::Result<Vec<usize>> {
+    ParseContext::new(input).lines().map(count_wins).collect()
+}
+
+fn count_wins(line: Line) -> aoc::Result<usize> {
+    let bar = line
+        .text
+        .find('|')
+        .ok_or_else(|| line.error(line.text, "Expected a | between the numbers"))?;
+    let (winning, having) = (&line.text[..bar], &line.text[bar + 1..]);
+    // The first number is the number of the card.
+    let winning = numbers(&line, winning)
+        .skip(1)
+        .collect::<aoc::Result<HashSet<_>>>()?;
+    let having = numbers(&line, having).collect::<aoc::Result<Vec<_>>>()?;
+    Ok(having.iter().filter(|n| winning.contains(n)).count())
+}
+
+/// The numbers in `text`, a part of `line`, failing at the column of a bad
+/// number in the line.
+fn numbers<'a>(line: &'a Line, text: &'a str) -> impl Iterator<Item = aoc::Result<u32>> + 'a {
+    try_extract_numbers(text).map(move |n| n.map_err(|e| line.locate(e, text)))
+}
+
+pub fn part1(counts: &[usize]) -> u32 {
+    counts
+        .iter()
+        .filter_map(|&count| (count as u32).checked_sub(1).map(|exp| 2u32.pow(exp)))
+        .sum()
+}
+
+fn part2(counts: &[usize]) -> usize {
+    let mut cards: Vec<Cards> = counts
+        .iter()
+        .map(|&count| Cards::new(count))
+        .collect::<Vec<_>>();
+
+    for i in 0..cards.len() {
+        debug!("card: {:?}", cards[i]);
+        for y in i + 1..i + 1 + cards[i].winning_numbers {
+            let amount = cards[i].number_of_cards;
+            cards[y].add_cards(amount);
+        }
+    }
+
+    cards.iter().map(|card| card.number_of_cards).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc::answer_tests!(crate::DAY);
+
+    #[test]
+    fn bad_cards_are_errors() {
+        let error = counts("Card 1: 41 48 | 83 86\nCard 2: 13 32 20 16 61\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 2, column 1: Expected a | between the numbers: "Card 2: 13 32 20 16 61""#
+        );
+        let error = counts("Card 1: 41 48 | 83 8600000000000\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 1, column 20: Not a number: "8600000000000""#
+        );
+    }
+}
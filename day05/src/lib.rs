@@ -0,0 +1,149 @@
+use aoc::ranges::{RangeMap, RangeSet};
+use aoc::{parse, try_extract_array, Day, Error, TrySolution};
+use log::debug;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub const DAY: Day = Day {
+    number: 5,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day05>,
+};
+
+struct Day05;
+
+impl TrySolution for Day05 {
+    type Parsed<'a> = (Vec<u64>, Vec<RangeMap<u64>>);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn try_parse(input: &str) -> aoc::Result<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn try_part1((seeds, groups): &Self::Parsed<'_>) -> aoc::Result<Option<u64>> {
+        Ok(part1(seeds, groups))
+    }
+
+    fn try_part2((seeds, groups): &Self::Parsed<'_>) -> aoc::Result<Option<u64>> {
+        part2(seeds, groups)
+    }
+}
+
+fn part1(seeds: &[u64], groups: &[RangeMap<u64>]) -> Option<u64> {
+    seeds
+        .iter()
+        .map(|seed| map_seed(*seed, groups))
+        .inspect(|dest| debug!("Destination: {dest}"))
+        .min()
+}
+
+fn parse_input(input: &str) -> aoc::Result<(Vec<u64>, Vec<RangeMap<u64>>)> {
+    let mut blocks = parse::blocks(input);
+    let (_, seeds) = parse::labelled_numbers(blocks.next().unwrap_or_default())?;
+    let groups = blocks
+        .map(|block| {
+            let (_, rules) = parse::header(block);
+            let rules = rules
+                .lines()
+                .map(try_extract_array)
+                .collect::<aoc::Result<Vec<_>>>()?;
+            Ok(parse_group(&rules))
+        })
+        .collect::<aoc::Result<Vec<_>>>()?;
+    debug!("{groups:?}");
+    Ok((seeds, groups))
+}
+
+/// Rules are the destination start, the source start and the length.
+fn parse_group(rules: &[[u64; 3]]) -> RangeMap<u64> {
+    rules
+        .iter()
+        .map(|[destination, source, length]| (*source..source + length, *destination))
+        .collect()
+}
+
+fn map_seed(seed: u64, groups: &[RangeMap<u64>]) -> u64 {
+    groups.iter().fold(seed, |seed, group| group.map(seed))
+}
+
+/// The seeds are pairs of a start and a length.
+fn part2(seeds: &[u64], groups: &[RangeMap<u64>]) -> aoc::Result<Option<u64>> {
+    if !seeds.len().is_multiple_of(2) {
+        return Err(Error::new(format!(
+            "Expected seed ranges as pairs of a start and a length, found {} numbers",
+            seeds.len()
+        )));
+    }
+    let seeds = seeds
+        .chunks_exact(2)
+        .map(|pair| pair[0]..pair[0] + pair[1])
+        .collect::<RangeSet<_>>();
+    debug!("Seeds: {seeds:?}");
+
+    Ok(groups
+        .iter()
+        .fold(seeds, |seeds, group| group.map_set(&seeds))
+        .min())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    aoc::answer_tests!(crate::DAY);
+
+    #[test]
+    fn one_step_split_whole_range_inside_rule() {
+        let seeds = RangeSet::from(79..93);
+        let group = parse_group(&[[50, 98, 2], [52, 50, 48]]);
+
+        let result = group.map_set(&seeds);
+
+        assert_eq!(result, RangeSet::from(81..95));
+    }
+
+    #[test]
+    fn map_seeds_with_middle_part_inside_rule() {
+        let seeds = 55..68;
+        let group = parse_group(&[[50, 60, 2]]);
+
+        let result = seeds.map(|seed| group.map(seed)).collect::<Vec<_>>();
+
+        assert_eq!(result.len(), 13);
+        assert_eq!(
+            result,
+            &[55, 56, 57, 58, 59, 50, 51, 62, 63, 64, 65, 66, 67]
+        );
+    }
+
+    #[test]
+    fn one_step_split_middle_part_inside_rule() {
+        let seeds = RangeSet::from(55..68);
+        let group = parse_group(&[[50, 60, 2]]);
+
+        let result = group.map_set(&seeds);
+
+        assert_eq!(result, RangeSet::from_iter([50..52, 55..60, 62..68]));
+    }
+
+    #[test]
+    fn seed_ranges_come_in_pairs() {
+        let error = part2(&[79, 14, 55], &[]).unwrap_err();
+        assert_eq!(
+            error.message,
+            "Expected seed ranges as pairs of a start and a length, found 3 numbers"
+        );
+        assert_eq!(part2(&[79, 14], &[]).unwrap(), Some(79));
+    }
+
+    #[test]
+    fn no_overflow_near_u32_max() {
+        let group = parse_group(&[[0, 4_294_967_290, 6]]);
+
+        assert_eq!(group.map(4_294_967_295), 5);
+        assert_eq!(group.map(4_294_967_296), 4_294_967_296);
+    }
+}
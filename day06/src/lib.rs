@@ -0,0 +1,132 @@
+use aoc::context::{Line, ParseContext};
+use aoc::{try_extract_numbers, Day, Error, TrySolution};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub const DAY: Day = Day {
+    number: 6,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day06>,
+};
+
+struct Day06;
+
+impl TrySolution for Day06 {
+    type Parsed<'a> = Vec<(u64, u64)>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn try_parse(input: &str) -> aoc::Result<Vec<(u64, u64)>> {
+        let mut lines = ParseContext::new(input).lines();
+        let (Some(times), Some(distances)) = (lines.next(), lines.next()) else {
+            return Err(Error::new(
+                "Expected a line of times and a line of distances",
+            ));
+        };
+        let (times, distances) = (numbers(&times)?, numbers(&distances)?);
+        if times.len() != distances.len() {
+            return Err(Error::new(format!(
+                "Expected as many distances as times, found {} and {}",
+                distances.len(),
+                times.len()
+            )));
+        }
+        Ok(times.into_iter().zip(distances).collect())
+    }
+
+    fn try_part1(races: &Vec<(u64, u64)>) -> aoc::Result<Option<u32>> {
+        Ok(Some(part1(races)))
+    }
+
+    fn try_part2(races: &Vec<(u64, u64)>) -> aoc::Result<Option<u32>> {
+        part2(races).map(Some)
+    }
+}
+
+fn numbers(line: &Line) -> aoc::Result<Vec<u64>> {
+    try_extract_numbers(line.text)
+        .map(|n| n.map_err(|e| line.locate(e, line.text)))
+        .collect()
+}
+
+fn part1(races: &[(u64, u64)]) -> u32 {
+    races
+        .iter()
+        .map(|(time, distance)| count_win_strategies(*time, *distance))
+        .product()
+}
+
+fn count_win_strategies(time: u64, record_distance: u64) -> u32 {
+    // The equation for checking if a particular time of button-pressing leads to a new record is:
+    // button_time * (time - button_time) > record_distance
+    // Solving for button_time gives us:
+    // time/2 - sqrt((time/2)^2 - record_distance) < button_time < time/2 + sqrt((time/2)^2 - record_distance)
+
+    // Solve the roots of the paraboloid:
+    let b = time as f64;
+    let c = record_distance as f64;
+    let half_b = b / 2f64;
+    let root = (half_b * half_b - c).sqrt();
+    let x1 = half_b - root;
+    let x2 = half_b + root;
+
+    // Count only inside the roots, excluding the roots if they are integer:
+    let start = x1.floor() as u64 + 1;
+    let end = x2.ceil() as u64 - 1;
+
+    // Include both start and end, so + 1
+    (end - start + 1) as u32
+}
+
+fn part2(races: &[(u64, u64)]) -> aoc::Result<u32> {
+    let time = join_digits(races.iter().map(|(time, _)| *time))
+        .ok_or_else(|| Error::new("The joined time does not fit in 64 bits"))?;
+    let distance = join_digits(races.iter().map(|(_, distance)| *distance))
+        .ok_or_else(|| Error::new("The joined distance does not fit in 64 bits"))?;
+    Ok(count_win_strategies(time, distance))
+}
+
+/// The digits of all the numbers as one number, or `None` if it is too big.
+fn join_digits(numbers: impl Iterator<Item = u64>) -> Option<u64> {
+    numbers.into_iter().try_fold(0u64, |joined, n| {
+        joined
+            .checked_mul(10u64.checked_pow(n.checked_ilog10().unwrap_or(0) + 1)?)?
+            .checked_add(n)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc::answer_tests!(crate::DAY);
+
+    #[test]
+    fn needs_times_and_distances() {
+        assert!(Day06::try_parse("Time: 7 15 30\n").is_err());
+        let error =
+            Day06::try_parse("Time: 7 15 30\nDistance: 9 40 99999999999999999999\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 2, column 16: Not a number: "99999999999999999999""#
+        );
+        let error = Day06::try_parse("Time: 7 15 30\nDistance: 9 40\n").unwrap_err();
+        assert_eq!(
+            error.message,
+            "Expected as many distances as times, found 2 and 3"
+        );
+        let races = Day06::try_parse("Time: 7 15\nDistance: 9999999999 9999999999\n").unwrap();
+        assert_eq!(
+            part2(&races).unwrap_err().message,
+            "The joined distance does not fit in 64 bits"
+        );
+    }
+
+    #[test]
+    fn joins_digits() {
+        assert_eq!(join_digits([7, 15, 30].into_iter()), Some(71530));
+        assert_eq!(join_digits([0, 1].into_iter()), Some(1));
+    }
+}
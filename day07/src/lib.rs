@@ -0,0 +1,290 @@
+use aoc::context::{Line, ParseContext};
+use aoc::{Day, TrySolution};
+use itertools::Itertools;
+use log::debug;
+use std::cmp::Ord;
+use std::fmt::{self, Debug, Display};
+use std::str::FromStr;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub const DAY: Day = Day {
+    number: 7,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day07>,
+};
+
+struct Day07;
+
+impl TrySolution for Day07 {
+    type Parsed<'a> = Vec<(HandWithJacks, u32)>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn try_parse(input: &str) -> aoc::Result<Vec<(HandWithJacks, u32)>> {
+        ParseContext::new(input)
+            .lines()
+            .map(|line| parse_line(&line))
+            .collect()
+    }
+
+    fn try_part1(hands: &Vec<(HandWithJacks, u32)>) -> aoc::Result<Option<u64>> {
+        let hands = hands.iter().map(|(hand, bid)| (hand, *bid)).collect();
+        Ok(Some(get_sum_of_bids(hands)))
+    }
+
+    fn try_part2(hands: &Vec<(HandWithJacks, u32)>) -> aoc::Result<Option<u64>> {
+        let hands = hands
+            .iter()
+            .map(|(hand, bid)| (HandWithJokers::from(hand), *bid))
+            .collect();
+        Ok(Some(get_sum_of_bids(hands)))
+    }
+}
+
+fn parse_line(line: &Line<'_>) -> aoc::Result<(HandWithJacks, u32)> {
+    let (hand, bid) = line
+        .text
+        .split_once(' ')
+        .ok_or_else(|| line.error(line.text, "Expected a hand and a bid"))?;
+    let bid = bid
+        .parse::<u32>()
+        .map_err(|_| line.error(bid, "Bid not a number"))?;
+    let hand = hand
+        .parse::<HandWithJacks>()
+        .map_err(|e| line.error(e.token(hand), e.to_string()))?;
+    debug!("{hand:?} {bid}");
+    Ok((hand, bid))
+}
+
+fn get_sum_of_bids<T: Debug + Ord>(mut hands: Vec<(T, u32)>) -> u64 {
+    debug!("Start of new part");
+    hands.sort();
+
+    hands
+        .iter()
+        .for_each(|(hand, bid)| debug!("hand: {hand:?}, bid: {bid}"));
+
+    hands
+        .iter()
+        .map(|hand| hand.1 as u64)
+        .enumerate()
+        .inspect(|(n, bid)| debug!("{} * {} = {}", n + 1, bid, (n + 1) as u64 * bid))
+        .map(|(n, bid)| ((n as u64) + 1) * bid)
+        .sum()
+}
+
+#[derive(Ord, PartialEq, PartialOrd, Eq, Debug)]
+enum HandRank {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+trait Hand {
+    fn new(rank: HandRank, cards: Vec<u8>) -> Self;
+    fn get_card_value(c: char) -> Result<u8, HandParseError>;
+}
+
+#[derive(Ord, PartialEq, PartialOrd, Eq, Debug)]
+struct HandWithJokers(HandRank, Vec<u8>);
+
+impl Hand for HandWithJokers {
+    fn new(rank: HandRank, cards: Vec<u8>) -> Self {
+        Self(rank, cards)
+    }
+
+    fn get_card_value(c: char) -> Result<u8, HandParseError> {
+        get_card_value(c, 1)
+    }
+}
+
+impl FromStr for HandWithJokers {
+    type Err = HandParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let result = from_str(s, Self::get_card_value)?;
+        Ok(Self::new(result.0, result.1))
+    }
+}
+
+/// The same cards with the jacks played as jokers.
+impl From<&HandWithJacks> for HandWithJokers {
+    fn from(hand: &HandWithJacks) -> Self {
+        let cards = hand
+            .1
+            .iter()
+            .map(|card| if *card == 11 { 1 } else { *card })
+            .collect::<Vec<_>>();
+        Self::new(rank(&cards), cards)
+    }
+}
+
+#[derive(Ord, PartialEq, PartialOrd, Eq, Debug)]
+struct HandWithJacks(HandRank, Vec<u8>);
+impl Hand for HandWithJacks {
+    fn new(rank: HandRank, cards: Vec<u8>) -> Self {
+        Self(rank, cards)
+    }
+
+    fn get_card_value(c: char) -> Result<u8, HandParseError> {
+        get_card_value(c, 11)
+    }
+}
+
+impl FromStr for HandWithJacks {
+    type Err = HandParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let result = from_str(s, Self::get_card_value)?;
+        Ok(Self::new(result.0, result.1))
+    }
+}
+
+impl HandRank {
+    fn get_rank(sorted_cards: &[u8]) -> HandRank {
+        let mut runs: Vec<usize> = Vec::new();
+        for (_, group) in &sorted_cards.iter().group_by(|card| *card) {
+            runs.push(group.count());
+        }
+        runs.sort_by(|a, b| b.cmp(a));
+        match (runs.len(), runs[0]) {
+            (1, _) => HandRank::FiveOfAKind,
+            (2, 4) => HandRank::FourOfAKind,
+            (2, _) => HandRank::FullHouse,
+            (3, 3) => HandRank::ThreeOfAKind,
+            (3, _) => HandRank::TwoPair,
+            (4, _) => HandRank::OnePair,
+            _ => HandRank::HighCard,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum HandParseError {
+    NotFiveCards,
+    CardParseError(char),
+}
+
+impl HandParseError {
+    /// The part of `hand` the error is about.
+    fn token<'a>(&self, hand: &'a str) -> &'a str {
+        match self {
+            HandParseError::NotFiveCards => hand,
+            // Cards are parsed in order, so the first one like it is the one
+            // that failed.
+            HandParseError::CardParseError(c) => {
+                hand.find(*c).map_or(hand, |i| &hand[i..i + c.len_utf8()])
+            }
+        }
+    }
+}
+
+impl Display for HandParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandParseError::NotFiveCards => write!(f, "Hand is not five cards"),
+            HandParseError::CardParseError(_) => write!(f, "Card not found"),
+        }
+    }
+}
+
+fn get_card_value(c: char, j_value: u8) -> Result<u8, HandParseError> {
+    match (c.is_ascii_digit(), c) {
+        (true, _) => Ok(c.to_digit(10).unwrap() as u8),
+        (false, 'A') => Ok(14),
+        (false, 'K') => Ok(13),
+        (false, 'Q') => Ok(12),
+        (false, 'J') => Ok(j_value),
+        (false, 'T') => Ok(10),
+        _ => Err(HandParseError::CardParseError(c)),
+    }
+}
+
+fn from_str(
+    s: &str,
+    card_parser: fn(char) -> Result<u8, HandParseError>,
+) -> Result<(HandRank, Vec<u8>), HandParseError> {
+    debug!("Parsing {s}");
+    let cards = s.chars().map(card_parser).collect::<Result<Vec<_>, _>>()?;
+    if cards.len() != 5 {
+        return Err(HandParseError::NotFiveCards);
+    }
+    Ok((rank(&cards), cards))
+}
+
+/// The rank of the cards, with jokers, valued 1, played as the cards that
+/// give the best rank.
+fn rank(cards: &[u8]) -> HandRank {
+    let mut sorted_cards = cards.to_vec();
+    sorted_cards.sort();
+
+    debug!("Parsing {sorted_cards:?}");
+
+    let jokers = sorted_cards.iter().take_while(|c| **c == 1).count();
+    debug!("Number of jokers: {jokers}");
+
+    match jokers {
+        1 => find_highest_for_joker_position(&sorted_cards, &sorted_cards[jokers..], 0),
+        2 => {
+            let mut highest = HandRank::HighCard;
+            for card1 in &sorted_cards[jokers..] {
+                let mut replaced1 = sorted_cards.clone();
+                replaced1[0] = *card1;
+                let rank = find_highest_for_joker_position(&replaced1, &sorted_cards[jokers..], 1);
+                if rank > highest {
+                    highest = rank;
+                }
+            }
+            highest
+        }
+        3 => {
+            if sorted_cards[3] == sorted_cards[4] {
+                HandRank::FiveOfAKind
+            } else {
+                HandRank::FourOfAKind
+            }
+        }
+        4 | 5 => HandRank::FiveOfAKind,
+        _ => HandRank::get_rank(&sorted_cards),
+    }
+}
+
+fn find_highest_for_joker_position(
+    sorted_cards: &[u8],
+    non_jokers: &[u8],
+    joker_pos: usize,
+) -> HandRank {
+    non_jokers
+        .iter()
+        .map(|card| {
+            let mut replaced = sorted_cards.to_vec();
+            replaced[joker_pos] = *card;
+            replaced.sort();
+            HandRank::get_rank(&replaced)
+        })
+        .max()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc::answer_tests!(crate::DAY);
+
+    #[test]
+    fn points_at_unknown_card() {
+        let error = Day07::try_parse("32T3K 765\nT55X5 684\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 2, column 4: Card not found: "X""#
+        );
+    }
+}
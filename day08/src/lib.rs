@@ -0,0 +1,262 @@
+use aoc::context::ParseContext;
+use aoc::{parse, scan, Day, Error, TrySolution};
+use std::collections::HashMap;
+
+use aoc::math;
+use log::debug;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub const DAY: Day = Day {
+    number: 8,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day08>,
+};
+
+struct Day08;
+
+impl TrySolution for Day08 {
+    type Parsed<'a> = (&'a str, Nodes<'a>);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn try_parse(input: &str) -> aoc::Result<Self::Parsed<'_>> {
+        let (directions, nodes) = parse(input)?;
+        debug!("{directions:?}");
+        debug!("{nodes:?}");
+        Ok((directions, nodes))
+    }
+
+    fn try_part1((directions, nodes): &Self::Parsed<'_>) -> aoc::Result<Option<u64>> {
+        part1(directions, nodes).map(Some)
+    }
+
+    fn try_part2((directions, nodes): &Self::Parsed<'_>) -> aoc::Result<Option<u64>> {
+        part2(directions, nodes).map(Some)
+    }
+}
+
+/// The left and right node from each node.
+type Nodes<'a> = HashMap<&'a str, (&'a str, &'a str)>;
+
+/// Parses the directions and the nodes, checking that the directions are
+/// all `L` or `R` and that every node leads to nodes that exist.
+fn parse(input: &str) -> aoc::Result<(&str, Nodes<'_>)> {
+    let context = ParseContext::new(input);
+    let directions = input.lines().next().unwrap_or_default().trim();
+    if directions.is_empty() {
+        return Err(Error::new("Expected a line of L and R directions"));
+    }
+    if let Some((i, c)) = directions
+        .char_indices()
+        .find(|(_, c)| !matches!(c, 'L' | 'R'))
+    {
+        let token = &directions[i..i + c.len_utf8()];
+        return Err(context.error(token, "Not a direction, expected L or R"));
+    }
+
+    let mut blocks = parse::blocks(input).skip(1);
+    let lines = blocks
+        .next()
+        .unwrap_or_default()
+        .lines()
+        .map(|line| {
+            let (node, left, right) = scan!(line, "{} = ({}, {})", &str, &str, &str)?;
+            Ok((node, (left, right)))
+        })
+        .collect::<aoc::Result<Vec<_>>>()?;
+    let nodes = lines.iter().copied().collect::<Nodes>();
+    for target in lines.iter().flat_map(|(_, (left, right))| [left, right]) {
+        if !nodes.contains_key(target) {
+            return Err(context.error(target, "Unknown node"));
+        }
+    }
+
+    Ok((directions, nodes))
+}
+
+fn part1(directions: &str, nodes: &HashMap<&str, (&str, &str)>) -> aoc::Result<u64> {
+    let ghost =
+        Ghost::new("AAA", directions, nodes).ok_or_else(|| Error::new("There is no node AAA"))?;
+    ghost
+        .before
+        .iter()
+        .chain(&ghost.in_cycle)
+        .next()
+        .copied()
+        .ok_or_else(|| Error::new("No Z node can be reached from AAA"))
+}
+
+fn part2(directions: &str, nodes: &HashMap<&str, (&str, &str)>) -> aoc::Result<u64> {
+    let mut starts = nodes
+        .keys()
+        .filter(|key| key.ends_with('A'))
+        .collect::<Vec<_>>();
+    starts.sort();
+    let ghosts = starts
+        .into_iter()
+        .map(|start| {
+            let ghost = Ghost::new(start, directions, nodes)
+                .ok_or_else(|| Error::new("Unknown node on the way").with_token(*start))?;
+            if ghost.before.is_empty() && ghost.in_cycle.is_empty() {
+                return Err(Error::new(format!("No Z node can be reached from {start}")));
+            }
+            Ok(ghost)
+        })
+        .inspect(|ghost| debug!("{ghost:?}"))
+        .collect::<aoc::Result<Vec<_>>>()?;
+
+    // Before every ghost is in its cycle, the steps where they all are at a
+    // Z node are among the early ones of the ghost that gets there last.
+    let cycles_start = ghosts
+        .iter()
+        .map(|ghost| ghost.cycle_start)
+        .max()
+        .ok_or_else(|| Error::new("There is no node ending in A"))?;
+    let early = ghosts
+        .iter()
+        .flat_map(|ghost| ghost.before.iter().copied())
+        .filter(|step| ghosts.iter().all(|ghost| ghost.at_z(*step)))
+        .min();
+    if let Some(early) = early {
+        return Ok(early);
+    }
+
+    // After it, each choice of Z step within the cycles gives congruences.
+    let mut solutions = vec![(0, 1)];
+    for ghost in &ghosts {
+        solutions = solutions
+            .iter()
+            .flat_map(|solution| {
+                ghost.in_cycle.iter().filter_map(|step| {
+                    math::crt([*solution, (*step as i64, ghost.cycle_length as i64)])
+                })
+            })
+            .collect();
+    }
+    debug!("{solutions:?}");
+
+    solutions
+        .into_iter()
+        .map(|(step, modulus)| {
+            let (step, modulus) = (step as u64, modulus as u64);
+            step + cycles_start.saturating_sub(step).div_ceil(modulus) * modulus
+        })
+        .min()
+        .ok_or_else(|| Error::new("The ghosts are never all at Z nodes at the same time"))
+}
+
+/// The steps where a ghost is at a Z node: the ones in `before`, then from
+/// `cycle_start` on every step congruent to one in `in_cycle` modulo
+/// `cycle_length`.
+#[derive(Debug)]
+struct Ghost {
+    before: Vec<u64>,
+    cycle_start: u64,
+    cycle_length: u64,
+    in_cycle: Vec<u64>,
+}
+
+impl Ghost {
+    /// Follows the directions from `start` until the ghost is back at a node
+    /// at the same point of the directions. `None` if a node on the way does
+    /// not exist.
+    fn new(start: &str, directions: &str, nodes: &HashMap<&str, (&str, &str)>) -> Option<Ghost> {
+        let directions = directions.as_bytes();
+        let mut seen = HashMap::new();
+        let mut at_z = vec![];
+        let mut current = start;
+        for step in 0.. {
+            let index = step as usize % directions.len();
+            if let Some(cycle_start) = seen.insert((current, index), step) {
+                let (before, in_cycle) = at_z.into_iter().partition(|s| *s < cycle_start);
+                return Some(Ghost {
+                    before,
+                    cycle_start,
+                    cycle_length: step - cycle_start,
+                    in_cycle,
+                });
+            }
+            if current.ends_with('Z') {
+                at_z.push(step);
+            }
+            let (left, right) = nodes.get(current)?;
+            current = match directions[index] {
+                b'L' => left,
+                _ => right,
+            };
+        }
+        unreachable!()
+    }
+
+    fn at_z(&self, step: u64) -> bool {
+        if step < self.cycle_start {
+            self.before.contains(&step)
+        } else {
+            let step = self.cycle_start + (step - self.cycle_start) % self.cycle_length;
+            self.in_cycle.contains(&step)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc::answer_tests!(crate::DAY);
+
+    #[test]
+    fn rejects_bad_networks() {
+        let error = parse("LRX\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 1, column 3: Not a direction, expected L or R: "X""#
+        );
+        let error = parse("\n\nAAA = (ZZZ, ZZZ)\n").unwrap_err();
+        assert_eq!(error.message, "Expected a line of L and R directions");
+        let error = parse("L\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"line 3, column 8: Unknown node: "BBB""#
+        );
+    }
+
+    #[test]
+    fn part1_needs_a_way_to_z() {
+        let (directions, nodes) = parse(DAY.example("example2").unwrap()).unwrap();
+        assert_eq!(
+            part1(directions, &nodes).unwrap_err().message,
+            "There is no node AAA"
+        );
+        let (directions, nodes) =
+            parse("L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+        assert_eq!(
+            part1(directions, &nodes).unwrap_err().message,
+            "No Z node can be reached from AAA"
+        );
+    }
+
+    #[test]
+    fn part2_needs_ghosts_that_meet() {
+        let (directions, nodes) = parse("L\n\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+        assert_eq!(
+            part2(directions, &nodes).unwrap_err().message,
+            "There is no node ending in A"
+        );
+        let (directions, nodes) = parse("L\n\n11A = (11B, 11B)\n11B = (11A, 11A)\n").unwrap();
+        assert_eq!(
+            part2(directions, &nodes).unwrap_err().message,
+            "No Z node can be reached from 11A"
+        );
+        let (directions, nodes) = parse(
+            "L\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)\n22A = (22B, 22B)\n22B = (22Z, 22Z)\n22Z = (22B, 22B)\n",
+        )
+        .unwrap();
+        assert_eq!(
+            part2(directions, &nodes).unwrap_err().message,
+            "The ghosts are never all at Z nodes at the same time"
+        );
+    }
+}
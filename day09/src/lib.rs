@@ -0,0 +1,80 @@
+use aoc::parse_numbers;
+use aoc::{Day, Solution};
+use log::debug;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub const DAY: Day = Day {
+    number: 9,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day09>,
+};
+
+struct Day09;
+
+impl Solution for Day09 {
+    type Parsed<'a> = Vec<Vec<i64>>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Vec<Vec<i64>> {
+        let lines: Vec<Vec<i64>> = input.lines().map(parse_numbers::<i64, Vec<_>>).collect();
+
+        debug!("{lines:?}");
+        lines
+    }
+
+    fn part1(lines: &Vec<Vec<i64>>) -> Option<i64> {
+        Some(part1(lines))
+    }
+
+    fn part2(lines: &Vec<Vec<i64>>) -> Option<i64> {
+        Some(part2(lines))
+    }
+}
+
+fn part1(lines: &[Vec<i64>]) -> i64 {
+    lines.iter().map(|line| history(line)).map(predict).sum()
+}
+
+fn predict(history: Vec<Vec<i64>>) -> i64 {
+    history
+        .iter()
+        .rev()
+        .fold(0, |prediction, line| line.last().unwrap() + prediction)
+}
+
+fn history(line: &[i64]) -> Vec<Vec<i64>> {
+    let mut history = vec![line.to_vec()];
+    let mut i: usize = 0;
+    debug!("{line:?}");
+    while !history[i].iter().all(|a| *a == 0) {
+        let next = next_line(&history[i]);
+        debug!("{next:?}");
+        history.push(next);
+        i += 1;
+    }
+    history
+}
+
+fn next_line(line: &[i64]) -> Vec<i64> {
+    line.windows(2).map(|a| a[1] - a[0]).collect()
+}
+
+fn part2(lines: &[Vec<i64>]) -> i64 {
+    lines.iter().map(|line| history(line)).map(go_back).sum()
+}
+
+fn go_back(history: Vec<Vec<i64>>) -> i64 {
+    history
+        .iter()
+        .rev()
+        .fold(0, |prediction, line| line.first().unwrap() - prediction)
+}
+
+#[cfg(test)]
+mod tests {
+    aoc::answer_tests!(crate::DAY);
+}
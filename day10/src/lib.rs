@@ -0,0 +1,511 @@
+use aoc::geom::Direction4::{self, Down, Left, Right, Up};
+use aoc::grid::{Grid, Pos};
+use aoc::search;
+use aoc::{Day, TrySolution};
+use log::{debug, trace};
+use std::char;
+use std::collections::HashSet;
+use std::fmt::Display;
+use Pipe::*;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+#[derive(Eq, PartialEq, PartialOrd, Ord, Debug, Clone, Copy)]
+enum Pipe {
+    Vertical,
+    Horizontal,
+    TopRight,
+    TopLeft,
+    BottomLeft,
+    BottomRight,
+    Start,
+}
+
+impl Pipe {
+    fn is_connected(&self, direction: &Direction4) -> bool {
+        let dirs = match self {
+            Vertical => [Up, Down],
+            Horizontal => [Left, Right],
+            TopRight => [Up, Right],
+            TopLeft => [Up, Left],
+            BottomLeft => [Down, Left],
+            BottomRight => [Down, Right],
+            Start => return true,
+        };
+        dirs.contains(direction)
+    }
+
+    fn can_squeeze_through(&self, dir: &Direction4, quadrant: &Quadrant) -> bool {
+        match self {
+            Vertical => {
+                !(quadrant.is_left() && *dir == Right || quadrant.is_right() && *dir == Left)
+            }
+            Horizontal => {
+                !(quadrant.is_top() && *dir == Down || quadrant.is_bottom() && *dir == Up)
+            }
+            TopRight => match quadrant {
+                Quadrant::TopRight => ![Left, Down].contains(dir),
+                Quadrant::TopLeft => *dir != Right,
+                Quadrant::BottomRight => *dir != Up,
+                Quadrant::BottomLeft => true,
+            },
+            TopLeft => match quadrant {
+                Quadrant::TopRight => *dir != Left,
+                Quadrant::TopLeft => ![Right, Down].contains(dir),
+                Quadrant::BottomRight => true,
+                Quadrant::BottomLeft => ![Up].contains(dir),
+            },
+            BottomRight => match quadrant {
+                Quadrant::TopRight => *dir != Down,
+                Quadrant::TopLeft => true,
+                Quadrant::BottomRight => ![Left, Up].contains(dir),
+                Quadrant::BottomLeft => *dir != Right,
+            },
+            BottomLeft => match quadrant {
+                Quadrant::TopRight => true,
+                Quadrant::TopLeft => *dir != Down,
+                Quadrant::BottomRight => *dir != Left,
+                Quadrant::BottomLeft => ![Right, Up].contains(dir),
+            },
+            Start => false,
+        }
+    }
+}
+
+impl TryFrom<char> for Pipe {
+    type Error = char;
+
+    fn try_from(chr: char) -> Result<Self, Self::Error> {
+        match chr {
+            '|' => Ok(Vertical),
+            '-' => Ok(Horizontal),
+            'L' => Ok(TopRight),
+            'J' => Ok(TopLeft),
+            '7' => Ok(BottomLeft),
+            'F' => Ok(BottomRight),
+            'S' => Ok(Start),
+            _ => Err(chr),
+        }
+    }
+}
+
+impl TryInto<char> for Pipe {
+    type Error = char;
+
+    fn try_into(self) -> Result<char, Self::Error> {
+        match self {
+            Vertical => Ok('│'),
+            Horizontal => Ok('─'),
+            TopRight => Ok('└'),
+            TopLeft => Ok('┘'),
+            BottomLeft => Ok('┐'),
+            BottomRight => Ok('┌'),
+            Start => Ok('S'),
+        }
+    }
+}
+
+impl Display for Pipe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Vertical => '│',
+            Horizontal => '─',
+            TopRight => '└',
+            TopLeft => '┘',
+            BottomLeft => '┐',
+            BottomRight => '┌',
+            Start => 'S',
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Hash, Clone, Copy)]
+enum Quadrant {
+    TopRight,
+    TopLeft,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Quadrant {
+    fn is_top(&self) -> bool {
+        match self {
+            Quadrant::TopLeft | Quadrant::TopRight => true,
+            Quadrant::BottomLeft | Quadrant::BottomRight => false,
+        }
+    }
+
+    fn is_bottom(&self) -> bool {
+        match self {
+            Quadrant::TopLeft | Quadrant::TopRight => false,
+            Quadrant::BottomLeft | Quadrant::BottomRight => true,
+        }
+    }
+
+    fn is_left(&self) -> bool {
+        match self {
+            Quadrant::TopLeft | Quadrant::BottomLeft => true,
+            Quadrant::TopRight | Quadrant::BottomRight => false,
+        }
+    }
+    fn is_right(&self) -> bool {
+        match self {
+            Quadrant::TopLeft | Quadrant::BottomLeft => false,
+            Quadrant::TopRight | Quadrant::BottomRight => true,
+        }
+    }
+
+    fn next(&self, dir: &Direction4) -> (Quadrant, bool) {
+        match dir {
+            Up => match self {
+                Quadrant::TopRight => (Quadrant::BottomRight, true),
+                Quadrant::TopLeft => (Quadrant::BottomLeft, true),
+                Quadrant::BottomLeft => (Quadrant::TopLeft, false),
+                Quadrant::BottomRight => (Quadrant::TopRight, false),
+            },
+            Down => match self {
+                Quadrant::TopRight => (Quadrant::BottomRight, false),
+                Quadrant::TopLeft => (Quadrant::BottomLeft, false),
+                Quadrant::BottomLeft => (Quadrant::TopLeft, true),
+                Quadrant::BottomRight => (Quadrant::TopRight, true),
+            },
+            Left => match self {
+                Quadrant::TopRight => (Quadrant::TopLeft, false),
+                Quadrant::TopLeft => (Quadrant::TopRight, true),
+                Quadrant::BottomLeft => (Quadrant::BottomRight, true),
+                Quadrant::BottomRight => (Quadrant::BottomLeft, false),
+            },
+            Right => match self {
+                Quadrant::TopRight => (Quadrant::TopLeft, true),
+                Quadrant::TopLeft => (Quadrant::TopRight, false),
+                Quadrant::BottomLeft => (Quadrant::BottomRight, false),
+                Quadrant::BottomRight => (Quadrant::BottomLeft, true),
+            },
+        }
+    }
+}
+
+pub const DAY: Day = Day {
+    number: 10,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day10>,
+};
+
+struct Day10;
+
+impl TrySolution for Day10 {
+    type Parsed<'a> = Map;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn try_parse(input: &str) -> aoc::Result<Self::Parsed<'_>> {
+        parse(input)
+    }
+
+    fn try_part1((map, start_y, start_x): &Self::Parsed<'_>) -> aoc::Result<Option<u32>> {
+        part1(map, *start_y, *start_x).map(Some)
+    }
+
+    fn try_part2((map, start_y, start_x): &Self::Parsed<'_>) -> aoc::Result<Option<u32>> {
+        part2(map, *start_y, *start_x).map(Some)
+    }
+}
+
+/// The tiles of the map and the row and column of the start.
+type Map = (Grid<Option<Pipe>>, usize, usize);
+
+fn parse(input: &str) -> aoc::Result<Map> {
+    let map = Grid::try_parse(input, |c| match c {
+        '.' => Ok(None),
+        c => Pipe::try_from(c).map(Some).map_err(|_| "Not a pipe"),
+    })?;
+
+    debug!("{}", visualize(&map, &HashSet::new()));
+
+    let (start_y, start_x) = map
+        .position(|pipe| pipe == &Some(Start))
+        .ok_or_else(|| aoc::Error::new("No start tile S"))?;
+
+    debug!("Start point: {start_y}, {start_x}");
+
+    Ok((map, start_y, start_x))
+}
+
+fn try_move(dir: &Direction4, y: usize, x: usize, map: &Grid<Option<Pipe>>) -> Option<Pos> {
+    let pipe = map[(y, x)]?;
+    if !pipe.is_connected(dir) {
+        return None;
+    }
+    let (new_y, new_x) = map.step((y, x), *dir)?;
+    let next = map[(new_y, new_x)]?;
+    next.is_connected(&dir.reverse()).then_some((new_y, new_x))
+}
+
+/// The tiles of the loop through the start, in order from the start.
+fn find_loop(map: &Grid<Option<Pipe>>, start_y: usize, start_x: usize) -> aoc::Result<Vec<Pos>> {
+    search::walk_cycle((start_y, start_x), |&(y, x)| {
+        Direction4::ALL
+            .iter()
+            .filter_map(move |dir| try_move(dir, y, x, map))
+    })
+    .ok_or_else(|| aoc::Error::new("Start is not on a loop"))
+}
+
+fn part1(map: &Grid<Option<Pipe>>, start_y: usize, start_x: usize) -> aoc::Result<u32> {
+    let tiles = find_loop(map, start_y, start_x)?;
+    debug!("Loop length: {}", tiles.len());
+    Ok(tiles.len() as u32 / 2)
+}
+
+fn part2(input: &Grid<Option<Pipe>>, start_y: usize, start_x: usize) -> aoc::Result<u32> {
+    let mut map = Grid::filled(input.width() + 2, input.height() + 2, None::<Pipe>);
+    for (y, x) in find_loop(input, start_y, start_x)? {
+        map[(y + 1, x + 1)] = input[(y, x)];
+    }
+
+    debug!("{}", visualize(&map, &HashSet::new()));
+
+    let reachable = search::flood_fill((0, 0, Quadrant::TopLeft), |(y, x, q)| {
+        squeezable_edges(&map, *y, *x, q)
+    });
+
+    debug!("{}", visualize(&map, &reachable));
+
+    let reachable_coords = reachable
+        .into_iter()
+        .map(|(y, x, _)| (y, x))
+        .collect::<HashSet<_>>();
+
+    Ok(map
+        .iter()
+        .filter(|(pos, pipe)| pipe.is_none() && !reachable_coords.contains(pos))
+        .count() as u32)
+}
+
+fn visualize(map: &Grid<Option<Pipe>>, reached: &HashSet<(usize, usize, Quadrant)>) -> String {
+    let mut visual = map.map(|pipe| pipe.map(|p| p.try_into().unwrap()).unwrap_or('.'));
+    for (y, x, _) in reached {
+        visual[(*y, *x)] = 'O';
+    }
+    format!("\n{visual}")
+}
+
+fn try_squeeze(
+    dir: &Direction4,
+    y: usize,
+    x: usize,
+    quadrant: &Quadrant,
+    map: &Grid<Option<Pipe>>,
+) -> Option<(usize, usize, Quadrant)> {
+    if let Some(pipe) = map[(y, x)] {
+        if !pipe.can_squeeze_through(dir, quadrant) {
+            return None;
+        }
+    }
+
+    let (next_q, change_coords) = quadrant.next(dir);
+
+    let (new_y, new_x) = if change_coords {
+        map.step((y, x), *dir)?
+    } else {
+        (y, x)
+    };
+
+    Some((new_y, new_x, next_q))
+}
+
+fn squeezable_edges(
+    map: &Grid<Option<Pipe>>,
+    y: usize,
+    x: usize,
+    quadrant: &Quadrant,
+) -> Vec<(usize, usize, Quadrant)> {
+    let reachable = Direction4::ALL
+        .iter()
+        .filter_map(|dir| try_squeeze(dir, y, x, quadrant, map))
+        .collect();
+    trace!("From map coords ({y},{x},{quadrant:?}) we can get to: {reachable:?}");
+    reachable
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    aoc::answer_tests!(crate::DAY);
+
+    #[test]
+    fn points_at_unknown_tile() {
+        let error = parse("..F7.\n.FJ|.\nSJ.Lx\n").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "error: Not a pipe: \"x\"\n --> 3:5\n  |\n3 | SJ.Lx\n  |     ^"
+        );
+        assert_eq!(parse("..F7.\n").unwrap_err().to_string(), "No start tile S");
+    }
+
+    #[test]
+    fn can_squeeze_through_horizontal() {
+        let pipe = Pipe::Horizontal;
+        let cases = [
+            (Up, Quadrant::TopRight, true),
+            (Up, Quadrant::TopLeft, true),
+            (Up, Quadrant::BottomLeft, false),
+            (Up, Quadrant::BottomRight, false),
+            (Down, Quadrant::TopRight, false),
+            (Down, Quadrant::TopLeft, false),
+            (Down, Quadrant::BottomLeft, true),
+            (Down, Quadrant::BottomRight, true),
+            (Left, Quadrant::TopRight, true),
+            (Left, Quadrant::TopLeft, true),
+            (Left, Quadrant::BottomLeft, true),
+            (Left, Quadrant::BottomRight, true),
+            (Right, Quadrant::TopRight, true),
+            (Right, Quadrant::TopLeft, true),
+            (Right, Quadrant::BottomLeft, true),
+            (Right, Quadrant::BottomRight, true),
+        ];
+        for case in cases {
+            assert_eq!(case.2, pipe.can_squeeze_through(&case.0, &case.1));
+        }
+    }
+
+    #[test]
+    fn can_squeeze_through_vertical() {
+        let pipe = Pipe::Vertical;
+        let cases = [
+            (Up, Quadrant::TopRight, true),
+            (Up, Quadrant::TopLeft, true),
+            (Up, Quadrant::BottomLeft, true),
+            (Up, Quadrant::BottomRight, true),
+            (Down, Quadrant::TopRight, true),
+            (Down, Quadrant::TopLeft, true),
+            (Down, Quadrant::BottomLeft, true),
+            (Down, Quadrant::BottomRight, true),
+            (Left, Quadrant::TopRight, false),
+            (Left, Quadrant::TopLeft, true),
+            (Left, Quadrant::BottomLeft, true),
+            (Left, Quadrant::BottomRight, false),
+            (Right, Quadrant::TopRight, true),
+            (Right, Quadrant::TopLeft, false),
+            (Right, Quadrant::BottomLeft, false),
+            (Right, Quadrant::BottomRight, true),
+        ];
+        for case in cases {
+            assert_eq!(case.2, pipe.can_squeeze_through(&case.0, &case.1));
+        }
+    }
+
+    #[test]
+    fn can_squeeze_through_topleft() {
+        let pipe = Pipe::TopLeft;
+        let cases = [
+            (Up, Quadrant::TopRight, true),
+            (Up, Quadrant::TopLeft, true),
+            (Up, Quadrant::BottomLeft, false),
+            (Up, Quadrant::BottomRight, true),
+            (Down, Quadrant::TopRight, true),
+            (Down, Quadrant::TopLeft, false),
+            (Down, Quadrant::BottomLeft, true),
+            (Down, Quadrant::BottomRight, true),
+            (Left, Quadrant::TopRight, false),
+            (Left, Quadrant::TopLeft, true),
+            (Left, Quadrant::BottomLeft, true),
+            (Left, Quadrant::BottomRight, true),
+            (Right, Quadrant::TopRight, true),
+            (Right, Quadrant::TopLeft, false),
+            (Right, Quadrant::BottomLeft, true),
+            (Right, Quadrant::BottomRight, true),
+        ];
+        for case in cases {
+            println!("{case:?}");
+            assert_eq!(case.2, pipe.can_squeeze_through(&case.0, &case.1));
+        }
+    }
+
+    #[test]
+    fn can_squeeze_through_topright() {
+        let pipe = Pipe::TopRight;
+        let cases = [
+            (Up, Quadrant::TopRight, true),
+            (Up, Quadrant::TopLeft, true),
+            (Up, Quadrant::BottomLeft, true),
+            (Up, Quadrant::BottomRight, false),
+            (Down, Quadrant::TopRight, false),
+            (Down, Quadrant::TopLeft, true),
+            (Down, Quadrant::BottomLeft, true),
+            (Down, Quadrant::BottomRight, true),
+            (Left, Quadrant::TopRight, false),
+            (Left, Quadrant::TopLeft, true),
+            (Left, Quadrant::BottomLeft, true),
+            (Left, Quadrant::BottomRight, true),
+            (Right, Quadrant::TopRight, true),
+            (Right, Quadrant::TopLeft, false),
+            (Right, Quadrant::BottomLeft, true),
+            (Right, Quadrant::BottomRight, true),
+        ];
+        for case in cases {
+            println!("{case:?}");
+            assert_eq!(case.2, pipe.can_squeeze_through(&case.0, &case.1));
+        }
+    }
+
+    #[test]
+    fn can_squeeze_through_bottom_left() {
+        let pipe = Pipe::BottomLeft;
+        let cases = [
+            (Up, Quadrant::TopRight, true),
+            (Up, Quadrant::TopLeft, true),
+            (Up, Quadrant::BottomLeft, false),
+            (Up, Quadrant::BottomRight, true),
+            (Down, Quadrant::TopRight, true),
+            (Down, Quadrant::TopLeft, false),
+            (Down, Quadrant::BottomLeft, true),
+            (Down, Quadrant::BottomRight, true),
+            (Left, Quadrant::TopRight, true),
+            (Left, Quadrant::TopLeft, true),
+            (Left, Quadrant::BottomLeft, true),
+            (Left, Quadrant::BottomRight, false),
+            (Right, Quadrant::TopRight, true),
+            (Right, Quadrant::TopLeft, true),
+            (Right, Quadrant::BottomLeft, false),
+            (Right, Quadrant::BottomRight, true),
+        ];
+        for case in cases {
+            println!("{case:?}");
+            assert_eq!(case.2, pipe.can_squeeze_through(&case.0, &case.1));
+        }
+    }
+
+    #[test]
+    fn can_squeeze_through_bottom_right() {
+        let pipe = Pipe::BottomRight;
+        let cases = [
+            (Up, Quadrant::TopRight, true),
+            (Up, Quadrant::TopLeft, true),
+            (Up, Quadrant::BottomLeft, true),
+            (Up, Quadrant::BottomRight, false),
+            (Down, Quadrant::TopRight, false),
+            (Down, Quadrant::TopLeft, true),
+            (Down, Quadrant::BottomLeft, true),
+            (Down, Quadrant::BottomRight, true),
+            (Left, Quadrant::TopRight, true),
+            (Left, Quadrant::TopLeft, true),
+            (Left, Quadrant::BottomLeft, true),
+            (Left, Quadrant::BottomRight, false),
+            (Right, Quadrant::TopRight, true),
+            (Right, Quadrant::TopLeft, true),
+            (Right, Quadrant::BottomLeft, false),
+            (Right, Quadrant::BottomRight, true),
+        ];
+        for case in cases {
+            println!("{case:?}");
+            assert_eq!(case.2, pipe.can_squeeze_through(&case.0, &case.1));
+        }
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    aoc::build::embed_examples();
+}
@@ -0,0 +1,184 @@
+use aoc::geom::Point;
+use aoc::{Day, Solution};
+use std::collections::HashSet;
+
+use log::debug;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+const MULTIPLIER: usize = 1000000;
+
+pub const DAY: Day = Day {
+    number: 11,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day11>,
+};
+
+struct Day11;
+
+impl Solution for Day11 {
+    type Parsed<'a> = (Vec<Vec<char>>, HashSet<usize>, HashSet<usize>);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        parse(input)
+    }
+
+    fn part1((map, empty_columns, empty_rows): &Self::Parsed<'_>) -> Option<u64> {
+        Some(part1(&mut map.clone(), empty_columns, empty_rows))
+    }
+
+    fn part2((map, empty_columns, empty_rows): &Self::Parsed<'_>) -> Option<u64> {
+        Some(part2(&mut map.clone(), empty_columns, empty_rows))
+    }
+}
+
+fn parse(input: &str) -> (Vec<Vec<char>>, HashSet<usize>, HashSet<usize>) {
+    let lines = input
+        .lines()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let columns = (0..lines[0].len()).collect::<HashSet<usize>>();
+    let positions = lines
+        .iter()
+        .flat_map(|line| {
+            line.iter()
+                .enumerate()
+                .filter(|(_, c)| **c == '#')
+                .map(|(i, _)| i)
+        })
+        .collect::<HashSet<_>>();
+    let empty_columns = columns
+        .difference(&positions)
+        .cloned()
+        .collect::<HashSet<_>>();
+    debug!("empty_columns: {empty_columns:?}");
+
+    let rows = (0..lines[0].len()).collect::<HashSet<usize>>();
+    let positions = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.contains(&'#'))
+        .map(|(i, _)| i)
+        .collect::<HashSet<_>>();
+    let empty_rows = rows.difference(&positions).cloned().collect::<HashSet<_>>();
+    debug!("empty_rows: {empty_rows:?}");
+
+    debug!("{}", visualize(&lines));
+
+    (lines, empty_columns, empty_rows)
+}
+
+fn visualize(map: &[Vec<char>]) -> String {
+    let mut chars = vec!['\n'];
+    for line in map {
+        for c in line {
+            chars.push(*c);
+        }
+        chars.push('\n');
+    }
+    chars.iter().collect()
+}
+
+fn part1(
+    map: &mut [Vec<char>],
+    empty_columns: &HashSet<usize>,
+    empty_rows: &HashSet<usize>,
+) -> u64 {
+    sum_distances(map, empty_columns, empty_rows, &2)
+}
+
+fn sum_distances(
+    map: &mut [Vec<char>],
+    empty_columns: &HashSet<usize>,
+    empty_rows: &HashSet<usize>,
+    multiplier: &usize,
+) -> u64 {
+    let mut sum = 0u64;
+    for y in 0..map.len() {
+        for x in 0..map[y].len() {
+            if map[y][x] == '#' {
+                for dist in distances_from(
+                    map,
+                    Point::new(y, x),
+                    empty_columns,
+                    empty_rows,
+                    multiplier,
+                ) {
+                    sum += dist;
+                }
+                map[y][x] = '.';
+                debug!("sum: {sum}");
+                debug!("{}", visualize(map));
+            }
+        }
+    }
+    sum
+}
+
+fn distances_from(
+    map: &[Vec<char>],
+    root: Point,
+    empty_columns: &HashSet<usize>,
+    empty_rows: &HashSet<usize>,
+    multiplier: &usize,
+) -> Vec<u64> {
+    let mut dists = vec![];
+    for (y, row) in map.iter().enumerate() {
+        for (x, c) in row.iter().enumerate() {
+            if *c == '#' {
+                let dist = distance(
+                    &root,
+                    &Point::new(y, x),
+                    empty_columns,
+                    empty_rows,
+                    multiplier,
+                );
+                debug!("{} {} -> {} {}: {}", root.y, root.x, y, x, dist);
+                dists.push(dist)
+            }
+        }
+    }
+    dists
+}
+
+fn distance(
+    start: &Point,
+    end: &Point,
+    empty_columns: &HashSet<usize>,
+    empty_rows: &HashSet<usize>,
+    multiplier: &usize,
+) -> u64 {
+    let expanded_rows = empty_rows
+        .iter()
+        .filter(|y| (start.y.min(end.y)..start.y.max(end.y)).contains(y))
+        .count();
+    let expanded_columns = empty_columns
+        .iter()
+        .filter(|x| (start.x.min(end.x)..start.x.max(end.x)).contains(x))
+        .count();
+
+    debug!("expanded_rows {expanded_rows:?}");
+    debug!("expanded_columns {expanded_columns:?}");
+
+    (start.manhattan(*end)
+        + expanded_rows * (multiplier-1)
+        + expanded_columns * (multiplier-1)) as u64
+}
+
+fn part2(
+    map: &mut [Vec<char>],
+    empty_columns: &HashSet<usize>,
+    empty_rows: &HashSet<usize>,
+) -> u64 {
+    sum_distances(map, empty_columns, empty_rows, &MULTIPLIER)
+}
+
+#[cfg(test)]
+mod tests {
+    aoc::answer_tests!(crate::DAY);
+}
@@ -0,0 +1,179 @@
+use aoc::{extract_numbers, Day, Solution};
+use itertools::Itertools;
+use log::debug;
+use rayon::prelude::*;
+
+pub const INPUT: &str = include_str!("../input.txt");
+//const INPUT: &str = include_str!("../example.txt");
+
+pub const DAY: Day = Day {
+    number: 12,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day12>,
+};
+
+struct Day12;
+
+impl Solution for Day12 {
+    type Parsed<'a> = Vec<&'a str>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Vec<&str> {
+        input.lines().collect()
+    }
+
+    fn part1(lines: &Vec<&str>) -> Option<u64> {
+        Some(part1(lines))
+    }
+
+    fn part2(lines: &Vec<&str>) -> Option<u64> {
+        Some(part2(lines))
+    }
+}
+
+fn part1(lines: &[&str]) -> u64 {
+    lines
+        .iter()
+        .map(|line| line.split_once(' ').unwrap())
+        .map(|(springs, groups)| (springs, extract_numbers(groups).collect::<Vec<_>>()))
+        //.inspect(|(springs, groups)| debug!("{springs:?} {groups:?}"))
+        .map(|(springs, groups)| guess_springs(springs, &groups))
+        //.inspect(|count| debug!("Count: {count}"))
+        .sum()
+}
+
+fn part2(lines: &[&str]) -> u64 {
+    lines
+        .par_iter()
+        .map(|line| line.split_once(' ').unwrap())
+        .map(|(springs, groups)| {
+            (
+                (springs.to_owned() + "?")
+                    .repeat(5)
+                    .chars()
+                    .dropping_back(1)
+                    .collect::<String>(),
+                (groups.to_owned() + ",")
+                    .repeat(5)
+                    .chars()
+                    .dropping_back(1)
+                    .collect::<String>(),
+            )
+        })
+        .map(|(springs, groups)| (springs, extract_numbers(&groups).collect::<Vec<_>>()))
+        .inspect(|(springs, groups)| debug!("{springs:?} {groups:?}"))
+        .map(|(_springs, _groups)| guess_springs(&_springs, &_groups)) // guess_springs(&springs, &groups))
+        .inspect(|count| debug!("Count: {count}"))
+        .sum()
+}
+
+fn guess_springs(springs: &str, groups: &[usize]) -> u64 {
+    let size = springs.len();
+    let empties = size - groups.iter().sum::<usize>();
+    let springs = springs.chars().collect::<Vec<_>>();
+
+    (0..empties).into_par_iter()
+        .filter(|i| !springs[0..*i].contains(&'#'))
+        .map(|i| recursive(&springs, groups, 1, i, i, &empties))
+        .sum()
+}
+
+fn recursive(
+    springs: &[char],
+    groups: &[usize],
+    spaces: usize,
+    number_of_spaces: usize,
+    number_of_all_springs: usize,
+    empties: &usize,
+) -> u64 {
+    //debug!("Recursive: springs: {springs}, groups: {groups:?}, spaces: {spaces:?}, number_of_spaces: {number_of_spaces}, number_of_all_springs: {number_of_all_springs}, empties: {empties}");
+    let next_group = groups[spaces - 1];
+
+    if spaces == groups.len() {
+        /*
+               let reconstructed = (0..spaces.len())
+                   .flat_map(|i| vec![vec!['.'; spaces[i]], vec!['#'; groups[i]]])
+                   .flatten()
+                   .collect::<Vec<char>>();
+
+               let matches = springs.iter().enumerate().all(|(i, c)| match c {
+                   '?' => true,
+                   '#' => reconstructed.len() > i && reconstructed[i] == '#',
+                   '.' => reconstructed.len() <= i || reconstructed[i] == '.',
+                   _ => panic!("Unknown spring {c}"),
+               });
+
+               //debug!("matches: {matches}, reconstructed {reconstructed:?}");
+        */
+
+        if springs[number_of_all_springs..number_of_all_springs + next_group].contains(&'.') {
+            return 0;
+        }
+        if springs[number_of_all_springs + next_group..].contains(&'#') {
+            return 0;
+        }
+
+        return 1;
+    }
+    if springs[number_of_all_springs..number_of_all_springs + next_group].contains(&'.') {
+        /*
+        let reconstructed = (0..spaces.len())
+            .flat_map(|i| vec![vec!['.'; spaces[i]], vec!['#'; groups[i]]])
+            .flatten()
+            .collect::<Vec<char>>();
+        debug!("{reconstructed:?}");
+
+        debug!("1st branch Pruning branches starting with {spaces:?} + {i}");
+        debug!(
+            "{:?},{}",
+            number_of_all_springs..number_of_all_springs + next_group,
+            &springs[number_of_all_springs..=number_of_all_springs + i]
+        );
+        */
+        return 0;
+    }
+    let mut stop = false;
+    (1..=(empties - number_of_spaces))
+        .map(|i| {
+            if stop || springs[number_of_all_springs + next_group + i - 1] == '#' {
+                /*
+                let reconstructed = (0..spaces.len())
+                .flat_map(|i| vec![vec!['.'; spaces[i]], vec!['#'; groups[i]]])
+                .flatten()
+                .collect::<Vec<char>>();
+                debug!("{reconstructed:?}");
+                debug!("2nd branch Pruning branches starting with {spaces:?} + {i}");
+                debug!(
+                    "{}, {}, {:?},{:?}",
+                    (number_of_all_springs + next_group),
+                    number_of_all_springs + next_group + i,
+                    number_of_all_springs + next_group..number_of_all_springs + next_group + i,
+                    &springs[number_of_all_springs + next_group..number_of_all_springs + next_group + i]
+                );
+                */
+                stop = true;
+                0
+            } else {
+                recursive(
+                    springs,
+                    groups,
+                    spaces + 1,
+                    number_of_spaces + i,
+                    number_of_all_springs + i + groups[spaces - 1],
+                    empties,
+                )
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(dead_code)]
+    const PERFECT_RECORD: &str = include_str!("../perfect-records.txt");
+
+    aoc::answer_tests!(crate::DAY);
+}
@@ -0,0 +1,138 @@
+use aoc::{Day, Solution};
+use std::cmp::{min, max};
+
+use log::debug;
+use itertools::Itertools;
+
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub const DAY: Day = Day {
+    number: 13,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<Day13>,
+};
+
+struct Day13;
+
+impl Solution for Day13 {
+    type Parsed<'a> = Vec<&'a str>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Vec<&str> {
+        input.lines().collect()
+    }
+
+    fn part1(_lines: &Vec<&str>) -> Option<u64> {
+        None
+    }
+
+    fn part2(lines: &Vec<&str>) -> Option<u64> {
+        Some(part2(lines))
+    }
+}
+
+fn check_mirror_vertical(pattern: &[Vec<char>], must_include_column: usize) -> Option<usize> {
+    let start = max(1, must_include_column/2+must_include_column%2);
+    let end = min(pattern[0].len(), (pattern[0].len()+must_include_column)/2);
+    for x in start..end {
+        if check_mirror_vertical_for_x(pattern, x) {
+            debug!("{start}..{end}, must: {must_include_column}, dividor: {x}, pattern len: {}",pattern.len());
+            return Some(x);
+        }
+    }
+    None
+}
+
+fn check_mirror_vertical_for_x(pattern: &[Vec<char>], dividor:usize) -> bool {
+    // Double D
+    let dd = dividor +dividor;
+    for row in pattern {
+        for x in dividor..min(dd, row.len()) {
+            let anti_x = dd - x - 1;
+            if row[x] != row[anti_x] {return false;}
+        }
+    }
+    true
+}
+
+fn check_mirror_horizontal(pattern: &[Vec<char>], must_include_row: usize) -> Option<usize> {
+    let start = max(1, must_include_row/2+must_include_row%2);
+    let end = min(pattern.len(), (pattern.len()+must_include_row)/2+1);
+    for y in start..end {
+        if check_mirror_horizontal_for_y(pattern, y) {
+            debug!("{start}..{end}, must: {must_include_row}, dividor: {y}, pattern len: {}",pattern.len());
+            return Some(y);
+        }
+    }
+    None
+}
+
+fn check_mirror_horizontal_for_y(pattern: &[Vec<char>], dividor:usize) -> bool {
+    // Double Dividor
+    let dd = dividor + dividor;
+    for x in 0..pattern[0].len() {
+        for y in dividor..min(dd, pattern.len()) {
+            let anti_y = dd - y - 1;
+            if pattern[y][x] != pattern[anti_y][x] {return false;}
+        }
+    }
+    true
+}
+
+
+fn part2(lines: &[&str]) -> u64 {
+    
+    let groups = lines.iter().group_by(|a| a.is_empty())
+    .into_iter()
+    .filter(|(empty, _)| !empty)
+    .map(|(_, group)| group.collect::<Vec<_>>())
+    .collect::<Vec<_>>();
+
+    let mut sum: u64 = 0;
+    
+    for group in groups {
+        for &line in &group {
+            debug!("{line}");
+        }
+        let group = group.into_iter().map(|line| line.chars().collect::<Vec<_>>()).collect::<Vec<_>>();
+        sum += find_smudge(group);
+    }
+    sum
+}
+
+fn find_smudge(mut group: Vec<Vec<char>>) -> u64{
+    for y in 0..group.len() {
+        for x in 0..group[y].len() {
+            let c = group[y][x];
+            group[y][x] = if c=='.' {'#'} else {'.'};
+            let vertical_value = check_mirror_vertical(&group, x);
+            debug!("{y},{x}");
+            if let Some(h) = vertical_value {
+                for line in &group {
+                        debug!("{}",line.iter().collect::<String>());
+                    }
+                    debug!("{vertical_value:?}");
+                    return h as u64;
+            }
+            let horizontal_value = check_mirror_horizontal(&group, y);
+            if let Some(v) = horizontal_value {
+                for line in &group {
+                    debug!("{}",line.iter().collect::<String>());
+                }
+                debug!("{:?}", horizontal_value.map(|v|v*100));
+                return (v as u64)*100;
+            }
+            group[y][x] = c;
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    aoc::answer_tests!(crate::DAY);
+}
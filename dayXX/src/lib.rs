@@ -0,0 +1,36 @@
+use aoc::{Day, Solution};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub const DAY: Day = Day {
+    number: 0,
+    input: INPUT,
+    examples: include!(concat!(env!("OUT_DIR"), "/examples.rs")),
+    answers: include_str!("../answers.toml"),
+    solve: aoc::solve::<DayXX>,
+};
+
+struct DayXX;
+
+impl Solution for DayXX {
+    type Parsed<'a> = Vec<&'a str>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Vec<&str> {
+        input.lines().collect()
+    }
+
+    fn part1(_lines: &Vec<&str>) -> Option<u32> {
+        None
+    }
+
+    fn part2(_lines: &Vec<&str>) -> Option<u32> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    aoc::answer_tests!(crate::DAY);
+}
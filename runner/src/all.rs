@@ -0,0 +1,336 @@
+use aoc::report::{self, RunRecord};
+use aoc::{Answers, Aoc, Day, Error, Run, Source, Verdict};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::Duration;
+
+/// A day run on its puzzle input.
+pub struct DayRun {
+    pub day: &'static Day,
+    pub aoc: Aoc,
+    pub run: Run,
+    /// Peak resident memory of the day alone, when it could be measured.
+    pub peak_memory: Option<u64>,
+}
+
+/// Runs every day on its puzzle input, only `part` if given, each day on its
+/// own thread if `parallel` is set. The runs are in the order of `days`.
+/// Parts that `answers.toml` lists as unsolved are skipped, and a day that
+/// panics is reported as failed instead of ending the whole run.
+///
+/// Allocations are counted for the whole process, so days cannot run in
+/// parallel when they are counted. The peak memory of each day is only
+/// measured when they run one at a time.
+pub fn run_all(
+    days: &[&'static Day],
+    part: Option<u8>,
+    parallel: bool,
+) -> Result<Vec<DayRun>, String> {
+    if parallel && aoc::allocations::enabled() {
+        return Err("--parallel does not work when allocations are counted".to_string());
+    }
+    let run_day = |day: &'static Day| {
+        let name = Source::Input.name();
+        let unsolved = day
+            .answers()
+            .map(|answers| answers.unsolved(&name).to_vec())
+            .unwrap_or_default();
+        let parts = [1, 2]
+            .into_iter()
+            .filter(|p| part.is_none_or(|part| part == *p) && !unsolved.contains(p))
+            .collect::<Vec<_>>();
+        let aoc = match parts[..] {
+            [part] => day.load(&Source::Input, Some(part))?,
+            _ => day.load(&Source::Input, part)?,
+        };
+        if parts.is_empty() {
+            return Ok(DayRun {
+                day,
+                aoc,
+                run: skipped(),
+                peak_memory: None,
+            });
+        }
+        let measured = !parallel && report::reset_peak_memory();
+        let run = panic::catch_unwind(AssertUnwindSafe(|| (day.solve)(&aoc)))
+            .unwrap_or_else(|panic| panicked(panic.as_ref()));
+        let peak_memory = report::peak_memory().filter(|_| measured);
+        Ok(DayRun {
+            day,
+            aoc,
+            run,
+            peak_memory,
+        })
+    };
+    if !parallel {
+        return days.iter().map(|day| run_day(day)).collect();
+    }
+    thread::scope(|scope| {
+        let threads = days
+            .iter()
+            .map(|day| scope.spawn(move || run_day(day)))
+            .collect::<Vec<_>>();
+        threads
+            .into_iter()
+            .map(|thread| {
+                thread
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+            })
+            .collect()
+    })
+}
+
+/// The run of a day whose parts are all skipped.
+fn skipped() -> Run {
+    Run {
+        parse: Duration::ZERO,
+        parse_allocs: None,
+        error: None,
+        parts: vec![],
+    }
+}
+
+/// The run of a day that panicked, failed with the panic message.
+fn panicked(panic: &(dyn Any + Send)) -> Run {
+    let message = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or(panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic");
+    Run {
+        error: Some(Error::new(format!("Panicked: {message}"))),
+        ..skipped()
+    }
+}
+
+impl DayRun {
+    /// The JSON record of the run, with the peak memory of the day alone.
+    pub fn record(&self) -> RunRecord {
+        RunRecord {
+            peak_memory_bytes: self.peak_memory,
+            ..RunRecord::new(self.day, &self.aoc, &self.run)
+        }
+    }
+}
+
+/// The table of a run of every day, and what went wrong in it.
+pub struct Summary {
+    pub table: String,
+    /// Answers that differ from the known ones.
+    pub wrong: Vec<String>,
+    /// Days or parts that failed, and answers files that could not be read.
+    pub failures: Vec<String>,
+}
+
+impl Summary {
+    /// Says how many answers were wrong and how many things failed, if any.
+    pub fn problems(&self) -> Option<String> {
+        let count = |n: usize, one: &str, many: &str| match n {
+            0 => None,
+            1 => Some(format!("1 {one}")),
+            n => Some(format!("{n} {many}")),
+        };
+        let counts = [
+            count(self.wrong.len(), "wrong answer", "wrong answers"),
+            count(self.failures.len(), "failure", "failures"),
+        ];
+        let counts = counts.into_iter().flatten().collect::<Vec<_>>();
+        (!counts.is_empty()).then(|| counts.join(" and "))
+    }
+}
+
+/// One part's cell of the table.
+struct Cell {
+    text: String,
+    time: Option<Duration>,
+}
+
+/// The table of the answers and times of every part, with the wrong answers
+/// and failures to list under it.
+pub fn summary(runs: &[DayRun]) -> Summary {
+    let mut rows = vec![];
+    let mut wrong = vec![];
+    let mut failures = vec![];
+    for DayRun { day, aoc, run, .. } in runs {
+        let answers = day.answers().unwrap_or_else(|e| {
+            failures.push(e);
+            Answers::default()
+        });
+        if let Some(e) = &run.error {
+            failures.push(format!("Day {} failed\n{e:#}", day.number));
+        }
+        let cells = [1, 2].map(|number| {
+            let Some(part) = run.part(number) else {
+                let text = if run.error.is_some() {
+                    "failed"
+                } else if answers.unsolved(aoc.input_name()).contains(&number) {
+                    "unsolved"
+                } else {
+                    ""
+                };
+                return Cell {
+                    text: text.to_string(),
+                    time: None,
+                };
+            };
+            let text = match (&part.error, part.answer.as_deref()) {
+                (Some(e), _) => {
+                    failures.push(format!("Day {} part {number} failed\n{e:#}", day.number));
+                    "failed".to_string()
+                }
+                (None, answer) => match answers.check(aoc.input_name(), number, answer) {
+                    Verdict::Unknown => answer.unwrap_or("not solved").to_string(),
+                    Verdict::Correct => format!("{} ✔", answer.unwrap_or_default()),
+                    Verdict::Wrong { expected } => {
+                        let answer = answer.unwrap_or("not solved");
+                        wrong.push(format!(
+                            "Day {} part {number}: {answer} is wrong, expected {expected}",
+                            day.number
+                        ));
+                        format!("{answer} ✘")
+                    }
+                },
+            };
+            Cell {
+                text,
+                time: Some(part.time),
+            }
+        });
+        rows.push((day.number, cells, run.total()));
+    }
+
+    let width = rows
+        .iter()
+        .flat_map(|(_, cells, _)| cells.iter().map(|cell| cell.text.chars().count()))
+        .chain(["Part 1".len()])
+        .max()
+        .unwrap_or_default();
+    let time = |time: Option<Duration>| time.map_or(String::new(), |time| format!("{time:.3?}"));
+    let mut table = format!(
+        "{:<5}{:<width$}{:>12}  {:<width$}{:>12}{:>12}\n",
+        "Day", "Part 1", "", "Part 2", "", "Total"
+    );
+    for (number, [part1, part2], total) in &rows {
+        table += &format!(
+            "{:<5}{:<width$}{:>12}  {:<width$}{:>12}{:>12.3?}\n",
+            number,
+            part1.text,
+            time(part1.time),
+            part2.text,
+            time(part2.time),
+            total
+        );
+    }
+    let total = rows.iter().map(|(_, _, total)| *total).sum::<Duration>();
+    table += &format!("{:<w$}{total:>12.3?}", "Total", w = 5 + 2 * width + 26);
+    Summary {
+        table,
+        wrong,
+        failures,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::{Error, PartRun};
+
+    static DAY: Day = Day {
+        number: 3,
+        input: "",
+        examples: &[],
+        answers: "[input]\npart1 = 4361\npart2 = 467835\n",
+        solve: |_| unreachable!(),
+    };
+
+    static UNSOLVED: Day = Day {
+        number: 12,
+        input: "",
+        examples: &[],
+        answers: "[input]\nunsolved = [2]\n",
+        solve: |aoc| {
+            assert!(!aoc.runs_part(2), "part 2 is unsolved");
+            panic!("part 1 panicked")
+        },
+    };
+
+    fn part(part: u8, answer: &str, micros: u64) -> PartRun {
+        PartRun {
+            part,
+            answer: Some(answer.to_string()),
+            error: None,
+            time: Duration::from_micros(micros),
+            allocs: None,
+        }
+    }
+
+    fn day_run(parse_error: Option<Error>, parts: Vec<PartRun>) -> DayRun {
+        DayRun {
+            day: &DAY,
+            aoc: Aoc::new(Source::Input, "", None),
+            run: Run {
+                parse: Duration::from_micros(10),
+                parse_allocs: None,
+                error: parse_error,
+                parts,
+            },
+            peak_memory: None,
+        }
+    }
+
+    #[test]
+    fn summary_marks_answers() {
+        let runs = [
+            day_run(None, vec![part(1, "4361", 20), part(2, "467", 30)]),
+            day_run(Some(Error::new("Bad input")), vec![]),
+        ];
+        let summary = summary(&runs);
+        let lines = summary.table.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "Day  Part 1              Part 2                   Total",
+                "3    4361 ✔    20.000µs  467 ✘     30.000µs    60.000µs",
+                "3    failed              failed                10.000µs",
+                "Total                                          70.000µs",
+            ]
+        );
+        assert_eq!(
+            summary.wrong,
+            ["Day 3 part 2: 467 is wrong, expected 467835"]
+        );
+        assert_eq!(summary.failures, ["Day 3 failed\nBad input"]);
+        assert_eq!(
+            summary.problems().as_deref(),
+            Some("1 wrong answer and 1 failure")
+        );
+    }
+
+    #[test]
+    fn skips_unsolved_parts_and_catches_panics() {
+        let runs = run_all(&[&UNSOLVED], None, false).unwrap();
+        let run = &runs[0].run;
+        assert!(run.parts.is_empty());
+        assert_eq!(
+            run.error.as_ref().map(Error::to_string).as_deref(),
+            Some("Panicked: part 1 panicked")
+        );
+
+        let runs = run_all(&[&UNSOLVED], Some(2), false).unwrap();
+        assert!(!runs[0].run.failed());
+        let summary = summary(&runs);
+        assert_eq!(
+            summary
+                .table
+                .lines()
+                .nth(1)
+                .unwrap()
+                .split_whitespace()
+                .collect::<Vec<_>>(),
+            ["12", "unsolved", "0.000ns"]
+        );
+        assert!(summary.problems().is_none());
+    }
+}
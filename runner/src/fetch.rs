@@ -0,0 +1,291 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const YEAR: u16 = 2023;
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const USER_AGENT: &str = "github.com/corvidian/aoc2023 runner";
+/// Least time between two requests to the site.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Sends the HTTP requests, so that the client can be pointed at a stub.
+pub trait Http {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<Response, String>;
+
+    fn post(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        form: &[(&str, &str)],
+    ) -> Result<Response, String>;
+}
+
+pub struct Ureq {
+    agent: ureq::Agent,
+}
+
+impl Ureq {
+    pub fn new() -> Ureq {
+        Ureq {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    fn request(&self, method: &str, url: &str, headers: &[(&str, &str)]) -> ureq::Request {
+        headers
+            .iter()
+            .fold(self.agent.request(method, url), |request, (name, value)| {
+                request.set(name, value)
+            })
+    }
+}
+
+impl Http for Ureq {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<Response, String> {
+        into_response(self.request("GET", url, headers).call())
+    }
+
+    fn post(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        form: &[(&str, &str)],
+    ) -> Result<Response, String> {
+        into_response(self.request("POST", url, headers).send_form(form))
+    }
+}
+
+fn into_response(result: Result<ureq::Response, ureq::Error>) -> Result<Response, String> {
+    let response = match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => return Err(e.to_string()),
+    };
+    Ok(Response {
+        status: response.status(),
+        body: response.into_string().map_err(|e| e.to_string())?,
+    })
+}
+
+/// The directory where inputs are cached, `AOC_CACHE_DIR` or `~/.cache/aoc`
+/// by default.
+pub fn default_cache_dir() -> Result<PathBuf, String> {
+    if let Ok(dir) = env::var("AOC_CACHE_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    env::var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| env::var("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .map(|cache| cache.join("aoc"))
+        .map_err(|_| "No cache directory, set AOC_CACHE_DIR".to_string())
+}
+
+/// Where the input of the day is cached.
+pub fn input_path(cache_dir: &Path, day: u8) -> PathBuf {
+    cache_dir
+        .join(YEAR.to_string())
+        .join(format!("day{day:02}.txt"))
+}
+
+/// The session token of the logged in user, from `AOC_SESSION`.
+pub fn session_from_env() -> Result<String, String> {
+    env::var("AOC_SESSION")
+        .map(|session| session.trim().to_string())
+        .map_err(|_| "Set AOC_SESSION to the session cookie of adventofcode.com".to_string())
+}
+
+/// Talks to the Advent of Code site for one user, caching what it gets.
+pub struct Client<H: Http> {
+    pub http: H,
+    pub base_url: String,
+    pub session: String,
+    pub cache_dir: PathBuf,
+    pub min_interval: Duration,
+}
+
+impl<H: Http> Client<H> {
+    pub fn new(http: H, session: String, cache_dir: PathBuf) -> Client<H> {
+        Client {
+            http,
+            base_url: BASE_URL.to_string(),
+            session,
+            cache_dir,
+            min_interval: MIN_INTERVAL,
+        }
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        input_path(&self.cache_dir, day)
+    }
+
+    /// Downloads the input of the day into the cache and returns its path.
+    /// Days already in the cache are not fetched again.
+    pub fn fetch_input(&self, day: u8) -> Result<PathBuf, String> {
+        let path = self.input_path(day);
+        if path.exists() {
+            return Err(format!("Day {day} is already cached in {}", path.display()));
+        }
+
+        let url = format!("{}/{YEAR}/day/{day}/input", self.base_url);
+        let response = self.get(&url)?;
+        match response.status {
+            200 => {}
+            404 => return Err(format!("Day {day} is not unlocked yet")),
+            400 | 401 => return Err("The session token was not accepted".to_string()),
+            status => return Err(format!("Fetching {url} failed with {status}")),
+        }
+
+        let dir = path.parent().unwrap();
+        fs::create_dir_all(dir).map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+        fs::write(&path, response.body)
+            .map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+        Ok(path)
+    }
+
+    fn get(&self, url: &str) -> Result<Response, String> {
+        self.wait_for_turn()?;
+        let cookie = format!("session={}", self.session);
+        self.http
+            .get(url, &[("Cookie", &cookie), ("User-Agent", USER_AGENT)])
+    }
+
+    pub(crate) fn post(&self, url: &str, form: &[(&str, &str)]) -> Result<Response, String> {
+        self.wait_for_turn()?;
+        let cookie = format!("session={}", self.session);
+        self.http.post(
+            url,
+            &[("Cookie", &cookie), ("User-Agent", USER_AGENT)],
+            form,
+        )
+    }
+
+    /// Sleeps until `min_interval` has passed since the last request, which
+    /// is kept in the cache so that it holds across runs.
+    fn wait_for_turn(&self) -> Result<(), String> {
+        let stamp = self.cache_dir.join(".last_request");
+        let last = fs::read_to_string(&stamp)
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(Duration::from_millis);
+        if let Some(wait) = last.and_then(|last| (last + self.min_interval).checked_sub(now())) {
+            thread::sleep(wait);
+        }
+
+        // Rounded up, so that the next wait is never short.
+        let millis = now().as_micros().div_ceil(1000);
+        fs::create_dir_all(&self.cache_dir)
+            .and_then(|_| fs::write(&stamp, millis.to_string()))
+            .map_err(|e| format!("Could not write {}: {e}", stamp.display()))
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Instant;
+
+    /// A request the stub server got.
+    struct Seen {
+        url: String,
+        cookie: Option<String>,
+        user_agent: Option<String>,
+    }
+
+    /// Serves `responses` in order on a local port, reporting the requests.
+    fn stub_server(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<Seen>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let request = server.recv().unwrap();
+                let header = |name: &'static str| {
+                    request
+                        .headers()
+                        .iter()
+                        .find(|h| h.field.equiv(name))
+                        .map(|h| h.value.to_string())
+                };
+                sender
+                    .send(Seen {
+                        url: request.url().to_string(),
+                        cookie: header("Cookie"),
+                        user_agent: header("User-Agent"),
+                    })
+                    .unwrap();
+                let response = tiny_http::Response::from_string(body).with_status_code(status);
+                request.respond(response).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    fn client(base_url: String, name: &str) -> Client<Ureq> {
+        let dir = env::temp_dir().join(format!("aoc_fetch_{}_{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut client = Client::new(Ureq::new(), "token".to_string(), dir);
+        client.base_url = base_url;
+        client.min_interval = Duration::ZERO;
+        client
+    }
+
+    #[test]
+    fn fetches_input_once() {
+        let (url, requests) = stub_server(vec![(200, "1\n2\n")]);
+        let client = client(url, "once");
+
+        let path = client.fetch_input(1).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n");
+        let seen = requests.recv().unwrap();
+        assert_eq!(seen.url, "/2023/day/1/input");
+        assert_eq!(seen.cookie.as_deref(), Some("session=token"));
+        assert_eq!(seen.user_agent.as_deref(), Some(USER_AGENT));
+
+        assert!(client
+            .fetch_input(1)
+            .unwrap_err()
+            .contains("already cached"));
+        assert!(requests.try_recv().is_err());
+        fs::remove_dir_all(&client.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn does_not_cache_errors() {
+        let (url, _requests) = stub_server(vec![(404, "Not found"), (400, "Bad session")]);
+        let client = client(url, "errors");
+
+        assert_eq!(
+            client.fetch_input(25).unwrap_err(),
+            "Day 25 is not unlocked yet"
+        );
+        assert!(!client.input_path(25).exists());
+        assert!(client.fetch_input(25).unwrap_err().contains("session"));
+        fs::remove_dir_all(&client.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn waits_between_requests() {
+        let (url, _requests) = stub_server(vec![(200, "1"), (200, "2")]);
+        let mut client = client(url, "wait");
+        client.min_interval = Duration::from_millis(300);
+
+        let start = Instant::now();
+        client.fetch_input(1).unwrap();
+        client.fetch_input(2).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        fs::remove_dir_all(&client.cache_dir).unwrap();
+    }
+}
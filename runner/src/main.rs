@@ -0,0 +1,449 @@
+use aoc::history::{self, HISTORY_FILE};
+use aoc::{BenchOptions, Day, Format, LogSpec, Source, Verdict};
+use fetch::{Client, Ureq};
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+mod all;
+mod fetch;
+mod scaffold;
+mod submit;
+
+const DAYS: &[&Day] = &[
+    &day01::DAY,
+    &day02::DAY,
+    &day03::DAY,
+    &day04::DAY,
+    &day05::DAY,
+    &day06::DAY,
+    &day07::DAY,
+    &day08::DAY,
+    &day09::DAY,
+    &day10::DAY,
+    &day11::DAY,
+    &day12::DAY,
+    &day13::DAY,
+];
+
+const USAGE: &str = "\
+Usage: aoc run <day> [part] [--input | --example [NAME] | --file PATH | --stdin] [--bench [N|TIME]] [--warmup N] [--baseline NAME] [--format text|json] [--log SPEC]
+       aoc run --all [part] [--parallel] [--format text|json] [--log SPEC]
+       aoc bench compare [--baseline NAME] [--threshold PERCENT]
+       aoc fetch <day> [--cache DIR]
+       aoc submit <day> <part> [--cache DIR]
+       aoc new <day> [--fetch] [--cache DIR]";
+
+/// Why a command stopped, which decides its exit status like `aoc::run_day`:
+/// 2 for bad arguments, 1 when what they asked for failed.
+#[derive(Debug, PartialEq)]
+enum Failure {
+    Usage(String),
+    Failed(String),
+}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Failure {
+        Failure::Failed(message)
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Usage(message) | Failure::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
+fn usage(message: impl Into<String>) -> Failure {
+    Failure::Usage(message.into())
+}
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let result = match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("fetch") => fetch(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("new") => new(&args[1..]),
+        Some("bench") if args.get(1).is_some_and(|arg| arg == "compare") => compare(&args[2..]),
+        _ => Err(usage(USAGE)),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            eprintln!("{failure}");
+            match failure {
+                Failure::Usage(_) => ExitCode::from(2),
+                Failure::Failed(_) => ExitCode::FAILURE,
+            }
+        }
+    }
+}
+
+fn run(all_args: &[String]) -> Result<(), Failure> {
+    let bench = BenchOptions::from_args(all_args).map_err(Failure::Usage)?;
+    let source = Source::from_args(all_args).map_err(Failure::Usage)?;
+    let format = Format::from_args(all_args).map_err(Failure::Usage)?;
+    let log = LogSpec::from_args(all_args).map_err(Failure::Usage)?;
+    if bench.is_some() && format == Format::Json {
+        return Err(usage("--format json does not work with --bench"));
+    }
+    let mut positional = vec![];
+    let (mut all, mut parallel) = (false, false);
+    let mut args = all_args.iter().peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--parallel" => parallel = true,
+            "--input" | "--stdin" => {}
+            "--example" => {
+                args.next_if(|name| Source::is_example_name(name));
+            }
+            "--bench" => {
+                args.next_if(|value| !value.starts_with("--"));
+            }
+            "--file" | "--warmup" | "--baseline" | "--format" | "--log" => {
+                args.next();
+            }
+            _ if arg.starts_with("--") => {
+                return Err(usage(format!("Unknown option {arg}\n{USAGE}")))
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    if all {
+        if source.is_some() || bench.is_some() {
+            return Err(usage(format!(
+                "--all runs every day on its puzzle input, without another input or --bench\n{USAGE}"
+            )));
+        }
+        let part = match positional[..] {
+            [] => None,
+            [part] => Some(parse_part(part)?),
+            _ => return Err(usage(USAGE)),
+        };
+        return run_all(part, parallel, format, log);
+    }
+    if parallel {
+        return Err(usage("--parallel only works with --all"));
+    }
+
+    let (number, part) = match positional[..] {
+        [day] => (parse_day(day)?, None),
+        [day, part] => (parse_day(day)?, Some(parse_part(part)?)),
+        _ => return Err(usage(USAGE)),
+    };
+    let day = find_day(number)?;
+
+    let aoc = day
+        .load(&source.unwrap_or_default(), part)
+        .map_err(Failure::Usage)?;
+    aoc::init_logging_with(&day.log_name(&aoc), log, format);
+    let failed = match (bench, format) {
+        (Some(options), _) => aoc::benchmark::run(day, &aoc, &options).is_err(),
+        (None, Format::Text) => aoc::bench(day, &aoc).failed(),
+        (None, Format::Json) => aoc::report::run_json(day, &aoc).failed(),
+    };
+    if failed {
+        return Err(Failure::Failed(format!("Day {number} failed")));
+    }
+    Ok(())
+}
+
+/// Runs every day on its puzzle input, optionally only one part, and prints a
+/// table of the answers and times, or a JSON record per day. Fails if any
+/// answer is wrong or any day fails.
+fn run_all(
+    part: Option<u8>,
+    parallel: bool,
+    format: Format,
+    log: Option<LogSpec>,
+) -> Result<(), Failure> {
+    aoc::init_logging_with("all", log, format);
+    let start = Instant::now();
+    let runs = all::run_all(DAYS, part, parallel)?;
+    let elapsed = start.elapsed();
+    let summary = all::summary(&runs);
+    match format {
+        Format::Text => {
+            println!("{}", summary.table);
+            println!("Ran {} days in {elapsed:.3?}", runs.len());
+        }
+        Format::Json => {
+            for run in &runs {
+                println!("{}", run.record().to_json());
+            }
+        }
+    }
+    for problem in summary.wrong.iter().chain(&summary.failures) {
+        eprintln!("{problem}");
+    }
+    match summary.problems() {
+        Some(problems) => Err(Failure::Failed(problems)),
+        None => Ok(()),
+    }
+}
+
+/// Downloads the input of a day into the cache, using the session in
+/// `AOC_SESSION`.
+fn fetch(args: &[String]) -> Result<(), Failure> {
+    let mut number = None;
+    let mut cache_dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cache" => {
+                cache_dir = Some(PathBuf::from(
+                    args.next()
+                        .ok_or_else(|| usage("--cache needs a directory"))?,
+                ))
+            }
+            _ if arg.starts_with("--") || number.is_some() => {
+                return Err(usage(format!("Unknown argument {arg}\n{USAGE}")))
+            }
+            _ => number = Some(parse_day(arg)?),
+        }
+    }
+    let number = number.ok_or_else(|| usage(USAGE))?;
+    let cache_dir = cache_dir.map_or_else(fetch::default_cache_dir, Ok)?;
+
+    let client = Client::new(Ureq::new(), fetch::session_from_env()?, cache_dir);
+    let path = client.fetch_input(number)?;
+    println!("Saved the input of day {number} to {}", path.display());
+    Ok(())
+}
+
+/// Solves a part on the puzzle input and submits the answer, unless earlier
+/// submissions or the known answers already rule it out. Only the puzzle
+/// input's answer is ever submitted.
+fn submit(args: &[String]) -> Result<(), Failure> {
+    let mut positional = vec![];
+    let mut cache_dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => {}
+            "--example" | "--file" | "--stdin" => {
+                return Err(usage(format!(
+                    "Only answers for the puzzle input can be submitted, not with {arg}"
+                )))
+            }
+            "--cache" => {
+                cache_dir = Some(PathBuf::from(
+                    args.next()
+                        .ok_or_else(|| usage("--cache needs a directory"))?,
+                ))
+            }
+            _ if arg.starts_with("--") => {
+                return Err(usage(format!("Unknown argument {arg}\n{USAGE}")))
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let [number, part] = positional[..] else {
+        return Err(usage(USAGE));
+    };
+    let (number, part) = (parse_day(number)?, parse_part(part)?);
+    let day = find_day(number)?;
+    let cache_dir = cache_dir.map_or_else(fetch::default_cache_dir, Ok)?;
+
+    let aoc = day
+        .load(&Source::Input, Some(part))
+        .map_err(Failure::Usage)?;
+    let run = (day.solve)(&aoc);
+    if let Some(e) = run.errors().next() {
+        return Err(Failure::Failed(format!("Day {number} failed\n{e:#}")));
+    }
+    let answer = run
+        .part(part)
+        .and_then(|run| run.answer.clone())
+        .ok_or_else(|| format!("Part {part} of day {number} is not solved"))?;
+    println!("Day {number} part {part}: {answer}");
+
+    match day.answers()?.check(aoc.input_name(), part, Some(&answer)) {
+        Verdict::Unknown => {}
+        Verdict::Correct => {
+            return Err(Failure::Failed(format!(
+                "{answer} is already known to be right"
+            )))
+        }
+        Verdict::Wrong { expected } => {
+            return Err(Failure::Failed(format!(
+                "answers.toml says the answer is {expected}"
+            )));
+        }
+    }
+
+    let client = Client::new(Ureq::new(), fetch::session_from_env()?, cache_dir);
+    let log = submit::Log::in_cache(&client.cache_dir);
+    let verdict = submit::submit(&client, &log, number, part, &answer)?;
+    println!("The answer is {verdict}");
+    if verdict == submit::Verdict::Correct {
+        println!("Add part{part} = {answer} to the [input] of day{number:02}/answers.toml");
+    }
+    Ok(())
+}
+
+/// Creates a new day from the `dayXX` template, with the cached input if
+/// there is one, fetching it first with `--fetch`.
+fn new(args: &[String]) -> Result<(), Failure> {
+    let mut number = None;
+    let mut fetch = false;
+    let mut cache_dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--fetch" => fetch = true,
+            "--cache" => {
+                cache_dir = Some(PathBuf::from(
+                    args.next()
+                        .ok_or_else(|| usage("--cache needs a directory"))?,
+                ))
+            }
+            _ if arg.starts_with("--") || number.is_some() => {
+                return Err(usage(format!("Unknown argument {arg}\n{USAGE}")))
+            }
+            _ => number = Some(parse_day(arg)?),
+        }
+    }
+    let number = number.ok_or_else(|| usage(USAGE))?;
+    let cache_dir = cache_dir.map_or_else(fetch::default_cache_dir, Ok)?;
+
+    let input = fetch::input_path(&cache_dir, number);
+    if fetch && !input.exists() {
+        Client::new(Ureq::new(), fetch::session_from_env()?, cache_dir).fetch_input(number)?;
+    }
+    let input = input.exists().then_some(input);
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let dir = scaffold::new_day(root, number, input.as_deref())?;
+    println!("Created {}", dir.display());
+    match input {
+        Some(input) => println!("with the input from {}", input.display()),
+        None => println!("with an empty input.txt"),
+    }
+    Ok(())
+}
+
+/// Compares the latest benchmark of every stage with the previous one or a
+/// baseline, failing if any got slower than the threshold.
+fn compare(args: &[String]) -> Result<(), Failure> {
+    let mut baseline = None;
+    let mut threshold = 10.0;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--baseline" => {
+                baseline = Some(
+                    args.next()
+                        .ok_or_else(|| usage("--baseline needs a name"))?,
+                )
+            }
+            "--threshold" => {
+                let percent = args
+                    .next()
+                    .ok_or_else(|| usage("--threshold needs a percentage"))?;
+                threshold = percent
+                    .trim_end_matches('%')
+                    .parse::<f64>()
+                    .map_err(|_| usage(format!("Not a percentage: {percent}")))?;
+            }
+            _ => return Err(usage(format!("Unknown argument {arg}\n{USAGE}"))),
+        }
+    }
+
+    let records = history::load(HISTORY_FILE)
+        .map_err(|e| format!("Could not read benchmark history {HISTORY_FILE}: {e}"))?;
+    let comparisons = history::compare(&records, baseline.map(String::as_str));
+    if comparisons.is_empty() {
+        return Err(Failure::Failed(
+            "Nothing to compare against, run a benchmark first".to_string(),
+        ));
+    }
+
+    println!(
+        "{:<5}{:<12}{:<8}{:>12}{:>12}{:>9}",
+        "Day", "Input", "Stage", "before", "after", "change"
+    );
+    let mut regressions = 0;
+    for comparison in &comparisons {
+        let (before, after) = (&comparison.before, &comparison.after);
+        let regressed = comparison.is_regression(threshold / 100.0);
+        regressions += regressed as usize;
+        println!(
+            "{:<5}{:<12}{:<8}{:>12.3?}{:>12.3?}{:>+8.1}%{}",
+            after.day,
+            after.input,
+            after.stage,
+            Duration::from_nanos(before.median_ns),
+            Duration::from_nanos(after.median_ns),
+            comparison.change() * 100.0,
+            if regressed { "  slower" } else { "" }
+        );
+    }
+
+    match regressions {
+        0 => Ok(()),
+        n => Err(Failure::Failed(format!(
+            "{n} stages got more than {threshold}% slower"
+        ))),
+    }
+}
+
+fn find_day(number: u8) -> Result<&'static Day, Failure> {
+    DAYS.iter()
+        .find(|day| day.number == number)
+        .copied()
+        .ok_or_else(|| usage(format!("Day {number} is not registered")))
+}
+
+/// Parses a day of December with a puzzle, 1 to 25.
+fn parse_day(day: &str) -> Result<u8, Failure> {
+    match day.parse::<u8>() {
+        Ok(number @ 1..=25) => Ok(number),
+        _ => Err(usage(format!("Not a day from 1 to 25: {day}\n{USAGE}"))),
+    }
+}
+
+fn parse_part(part: &str) -> Result<u8, Failure> {
+    match part {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(usage(format!("Part must be 1 or 2, not {part}\n{USAGE}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_1_to_25() {
+        assert_eq!(parse_day("1"), Ok(1));
+        assert_eq!(parse_day("25"), Ok(25));
+        for day in ["0", "26", "99", "x"] {
+            assert!(parse_day(day).is_err(), "{day}");
+        }
+    }
+
+    #[test]
+    fn bad_arguments_are_usage_errors() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert!(matches!(
+            run(&args(&["1", "--nope"])),
+            Err(Failure::Usage(_))
+        ));
+        assert!(matches!(run(&args(&["26"])), Err(Failure::Usage(_))));
+        assert!(matches!(run(&args(&["1", "3"])), Err(Failure::Usage(_))));
+        assert!(matches!(
+            compare(&args(&["--threshold", "x"])),
+            Err(Failure::Usage(_))
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files of the `dayXX` template that a new day gets.
+const TEMPLATE_FILES: &[&str] = &[
+    "Cargo.toml",
+    "build.rs",
+    "answers.toml",
+    "example.txt",
+    "src/lib.rs",
+    "src/main.rs",
+];
+
+/// Creates `dayNN` from the `dayXX` template in the workspace at `root` and
+/// registers it in the workspace and the runner. The input is copied from
+/// `input` when given, and left empty otherwise.
+pub fn new_day(root: &Path, day: u8, input: Option<&Path>) -> Result<PathBuf, String> {
+    let name = format!("day{day:02}");
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let template = root.join("dayXX");
+    for file in TEMPLATE_FILES {
+        let text = read(&template.join(file))?;
+        let text = text
+            .replace("dayxx", &name)
+            .replace("DayXX", &format!("Day{day:02}"))
+            .replace("number: 0,", &format!("number: {day},"));
+        write(&dir.join(file), &text)?;
+    }
+    let input = match input {
+        Some(input) => read(input)?,
+        None => String::new(),
+    };
+    write(&dir.join("input.txt"), &input)?;
+
+    edit(&root.join("Cargo.toml"), |text| {
+        insert_sorted(text, &format!("    \"{name}\","), |line| {
+            line.starts_with("    \"day")
+        })
+    })?;
+    edit(&root.join("runner/Cargo.toml"), |text| {
+        insert_sorted(
+            text,
+            &format!("{name} = {{ path = \"../{name}\" }}"),
+            |line| line.starts_with("day"),
+        )
+    })?;
+    edit(&root.join("runner/src/main.rs"), |text| {
+        insert_sorted(text, &format!("    &{name}::DAY,"), |line| {
+            line.starts_with("    &day") && line.ends_with("::DAY,")
+        })
+    })?;
+    Ok(dir)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Could not read {}: {e}", path.display()))
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    fs::create_dir_all(path.parent().unwrap())
+        .and_then(|_| fs::write(path, text))
+        .map_err(|e| format!("Could not write {}: {e}", path.display()))
+}
+
+fn edit(path: &Path, change: impl Fn(&str) -> Result<String, String>) -> Result<(), String> {
+    let text = change(&read(path)?).map_err(|e| format!("{}: {e}", path.display()))?;
+    write(path, &text)
+}
+
+/// Inserts `new` among the lines that are `peer`s, keeping them in order.
+fn insert_sorted(text: &str, new: &str, peer: impl Fn(&str) -> bool) -> Result<String, String> {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    if lines.contains(&new) {
+        return Ok(text.to_string());
+    }
+    let peers = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| peer(line))
+        .map(|(i, line)| (i, *line))
+        .collect::<Vec<_>>();
+    let (first, _) = peers
+        .first()
+        .ok_or_else(|| format!("Nowhere to add {}", new.trim()))?;
+    let at = peers
+        .iter()
+        .rfind(|(_, line)| *line < new)
+        .map_or(*first, |(i, _)| i + 1);
+
+    lines.insert(at, new);
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_in_order() {
+        let text = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day03\",\n]\n";
+        let peer = |line: &str| line.starts_with("    \"day");
+        assert_eq!(
+            insert_sorted(text, "    \"day02\",", peer).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day02\",\n    \"day03\",\n]\n"
+        );
+        assert_eq!(
+            insert_sorted(text, "    \"day14\",", peer).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day03\",\n    \"day14\",\n]\n"
+        );
+        assert_eq!(insert_sorted(text, "    \"day03\",", peer).unwrap(), text);
+        assert!(insert_sorted("members = []\n", "    \"day01\",", peer).is_err());
+    }
+
+    /// Copies the files of the workspace that `new_day` reads or changes.
+    fn copy_workspace(to: &Path) {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let files = TEMPLATE_FILES.iter().map(|file| format!("dayXX/{file}"));
+        for file in
+            files.chain(["Cargo.toml", "runner/Cargo.toml", "runner/src/main.rs"].map(String::from))
+        {
+            write(&to.join(&file), &read(&root.join(&file)).unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn creates_and_registers_day() {
+        let root = std::env::temp_dir().join(format!("aoc_new_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        copy_workspace(&root);
+        let input = root.join("cached.txt");
+        fs::write(&input, "1 2 3\n").unwrap();
+
+        let dir = new_day(&root, 14, Some(&input)).unwrap();
+        assert_eq!(dir, root.join("day14"));
+        assert!(read(&dir.join("Cargo.toml"))
+            .unwrap()
+            .contains("name = \"day14\""));
+        assert!(read(&dir.join("src/main.rs"))
+            .unwrap()
+            .contains("day14::DAY"));
+        let lib = read(&dir.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("number: 14,") && lib.contains("struct Day14;"));
+        assert_eq!(read(&dir.join("input.txt")).unwrap(), "1 2 3\n");
+        assert!(dir.join("answers.toml").exists());
+
+        assert!(read(&root.join("Cargo.toml"))
+            .unwrap()
+            .contains("    \"day13\",\n    \"day14\",\n    # The template"));
+        assert!(read(&root.join("runner/Cargo.toml"))
+            .unwrap()
+            .contains("day14 = { path = \"../day14\" }"));
+        assert!(read(&root.join("runner/src/main.rs"))
+            .unwrap()
+            .contains("    &day13::DAY,\n    &day14::DAY,\n"));
+
+        assert!(new_day(&root, 14, None).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,300 @@
+use crate::fetch::{Client, Http, YEAR};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooLow,
+    TooHigh,
+    Wrong,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::TooLow => "too low",
+            Verdict::TooHigh => "too high",
+            Verdict::Wrong => "wrong",
+        })
+    }
+}
+
+/// Sends answers to be judged, so that tests can use a mock.
+pub trait Transport {
+    fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, String>;
+}
+
+impl<H: Http> Transport for Client<H> {
+    fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, String> {
+        let url = format!("{}/{YEAR}/day/{day}/answer", self.base_url);
+        let level = part.to_string();
+        let response = self.post(&url, &[("level", &level), ("answer", answer)])?;
+        match response.status {
+            200 => parse_verdict(&response.body),
+            400 | 401 => Err("The session token was not accepted".to_string()),
+            status => Err(format!("Submitting to {url} failed with {status}")),
+        }
+    }
+}
+
+/// Reads the verdict from the page the site answers a submission with.
+fn parse_verdict(page: &str) -> Result<Verdict, String> {
+    if page.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if page.contains("your answer is too low") {
+        Ok(Verdict::TooLow)
+    } else if page.contains("your answer is too high") {
+        Ok(Verdict::TooHigh)
+    } else if page.contains("That's not the right answer") {
+        Ok(Verdict::Wrong)
+    } else if page.contains("You gave an answer too recently") {
+        let wait = page
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map_or(String::new(), |(wait, _)| format!(", {wait} left to wait"));
+        Err(format!("Answered too recently{wait}"))
+    } else if page.contains("You don't seem to be solving the right level") {
+        Err("That part is already solved or not unlocked yet".to_string())
+    } else {
+        Err("Could not find a verdict in the response".to_string())
+    }
+}
+
+/// An answer sent to the site and what it said.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+/// Answers submitted before the log was kept, taken from notes like day 13's
+/// `notes.txt`, and checked along with the log.
+const SEEDED: &str = include_str!("../submissions.jsonl");
+
+/// Every answer submitted for the year, one JSON record per line.
+pub struct Log {
+    pub path: PathBuf,
+}
+
+impl Log {
+    pub fn in_cache(cache_dir: &Path) -> Log {
+        Log {
+            path: cache_dir.join(YEAR.to_string()).join("submissions.jsonl"),
+        }
+    }
+
+    /// The submissions in the log, after the ones seeded from before it.
+    pub fn load(&self) -> Result<Vec<Submission>, String> {
+        let mut submissions = parse_submissions(
+            "runner/submissions.jsonl",
+            SEEDED.lines().map(|line| Ok(line.to_string())),
+        )?;
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(submissions),
+            Err(e) => return Err(format!("Could not read {}: {e}", self.path.display())),
+        };
+        submissions.extend(parse_submissions(
+            &self.path.display().to_string(),
+            BufReader::new(file).lines(),
+        )?);
+        Ok(submissions)
+    }
+
+    pub fn append(&self, submission: &Submission) -> Result<(), String> {
+        let write = || -> io::Result<()> {
+            fs::create_dir_all(self.path.parent().unwrap())?;
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            writeln!(file, "{}", serde_json::to_string(submission)?)
+        };
+        write().map_err(|e| format!("Could not write {}: {e}", self.path.display()))
+    }
+}
+
+fn parse_submissions(
+    name: &str,
+    lines: impl Iterator<Item = io::Result<String>>,
+) -> Result<Vec<Submission>, String> {
+    lines
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line.map_err(|e| e.to_string())?;
+            serde_json::from_str(&line).map_err(|e| format!("{name}:{}: {e}", i + 1))
+        })
+        .collect()
+}
+
+/// Refuses answers that earlier submissions already rule out: the same
+/// answer again, any answer to a solved part, or a number outside the
+/// bounds given by answers that were too low or too high.
+pub fn check(submissions: &[Submission], day: u8, part: u8, answer: &str) -> Result<(), String> {
+    let number = answer.parse::<i128>().ok();
+    for earlier in submissions
+        .iter()
+        .filter(|s| s.day == day && s.part == part)
+    {
+        if earlier.answer == answer {
+            return Err(format!(
+                "{answer} was already submitted and was {}",
+                earlier.verdict
+            ));
+        }
+        let bound = earlier.answer.parse::<i128>().ok();
+        match (earlier.verdict, number, bound) {
+            (Verdict::Correct, _, _) => {
+                return Err(format!("Already solved with {}", earlier.answer));
+            }
+            (Verdict::TooLow, Some(number), Some(bound)) if number <= bound => {
+                return Err(format!("{answer} is too low, {bound} already was"));
+            }
+            (Verdict::TooHigh, Some(number), Some(bound)) if number >= bound => {
+                return Err(format!("{answer} is too high, {bound} already was"));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Submits the answer unless the log rules it out, and records the verdict.
+pub fn submit(
+    transport: &impl Transport,
+    log: &Log,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<Verdict, String> {
+    check(&log.load()?, day, part, answer)?;
+    let verdict = transport.submit(day, part, answer)?;
+    log.append(&Submission {
+        day,
+        part,
+        answer: answer.to_string(),
+        verdict,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs()),
+    })?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::env;
+
+    struct Mock {
+        verdict: Verdict,
+        calls: Cell<usize>,
+    }
+
+    impl Mock {
+        fn new(verdict: Verdict) -> Mock {
+            Mock {
+                verdict,
+                calls: Cell::new(0),
+            }
+        }
+    }
+
+    impl Transport for Mock {
+        fn submit(&self, _day: u8, _part: u8, _answer: &str) -> Result<Verdict, String> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(self.verdict)
+        }
+    }
+
+    fn log(name: &str) -> Log {
+        let dir = env::temp_dir().join(format!("aoc_submit_{}_{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Log::in_cache(&dir)
+    }
+
+    #[test]
+    fn records_verdicts_and_refuses_resubmission() {
+        let log = log("again");
+        let mock = Mock::new(Verdict::Wrong);
+        assert_eq!(submit(&mock, &log, 13, 2, "abc"), Ok(Verdict::Wrong));
+        assert!(submit(&mock, &log, 13, 2, "abc").is_err());
+        assert_eq!(mock.calls.get(), 1);
+        assert_eq!(log.load().unwrap().last().unwrap().answer, "abc");
+        fs::remove_dir_all(log.path.parent().unwrap().parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let log = log("bounds");
+        assert_eq!(
+            submit(&Mock::new(Verdict::TooLow), &log, 12, 2, "20312"),
+            Ok(Verdict::TooLow)
+        );
+        assert_eq!(
+            submit(&Mock::new(Verdict::TooHigh), &log, 12, 2, "40000"),
+            Ok(Verdict::TooHigh)
+        );
+
+        let mock = Mock::new(Verdict::Correct);
+        assert!(submit(&mock, &log, 12, 2, "20290").is_err());
+        assert!(submit(&mock, &log, 12, 2, "40001").is_err());
+        assert_eq!(mock.calls.get(), 0);
+        assert_eq!(submit(&mock, &log, 12, 1, "20290"), Ok(Verdict::Correct));
+        assert_eq!(submit(&mock, &log, 12, 2, "30000"), Ok(Verdict::Correct));
+        assert!(submit(&mock, &log, 12, 2, "30001").is_err());
+        fs::remove_dir_all(log.path.parent().unwrap().parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn seeded_verdicts_are_checked() {
+        let mock = Mock::new(Verdict::Correct);
+        assert_eq!(
+            submit(&mock, &log("seeded"), 13, 2, "20290"),
+            Err("20290 is too low, 20312 already was".to_string())
+        );
+        assert_eq!(mock.calls.get(), 0);
+    }
+
+    #[test]
+    fn verdict_from_page() {
+        let page = |text: &str| format!("<main><article><p>{text}</p></article></main>");
+        assert_eq!(
+            parse_verdict(&page(
+                "That's the right answer! You are one gold star closer."
+            )),
+            Ok(Verdict::Correct)
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer; your answer is too low. Please wait one minute."
+            )),
+            Ok(Verdict::TooLow)
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer. Please wait one minute."
+            )),
+            Ok(Verdict::Wrong)
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "You gave an answer too recently. You have 42s left to wait."
+            )),
+            Err("Answered too recently, 42s left to wait".to_string())
+        );
+    }
+}